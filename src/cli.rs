@@ -5,17 +5,16 @@ use rustyline::hint::HistoryHinter;
 use colored::*;
 use rustyline::{CompletionType, Config, Editor};
 
-use std::process::Stdio;
 use std::rc::Rc;
 
-use crate::commands::{self, CommandType, ExternalCommand, InternalCommand};
+use crate::commands;
 use crate::completion::RushCompleter;
 use crate::error::ShellError;
+use crate::eval::{self, Scope};
+use crate::interrupt;
 use crate::parselex;
-use crate::parselex::parser::{ParsedCommand, ParsedPipeline};
-use crate::rushhelper::{PathChecker, RushHelper};
+use crate::rushhelper::{DirectoryCache, PathChecker, RushHelper};
 
-use crate::stream::RushStream;
 use crate::types::primary::{ToBaseView, Value};
 
 use crate::context::Context;
@@ -27,6 +26,7 @@ pub fn run() -> Result<(), Box<dyn std::error::Error>> {
 
     let mut context = Context::default();
     context.generate_externals();
+    interrupt::install();
 
     let ls = commands::ls::Ls;
     let ps = commands::ps::Ps;
@@ -34,6 +34,59 @@ pub fn run() -> Result<(), Box<dyn std::error::Error>> {
     let sortby = commands::sortby::SortBy;
     let take = commands::take::Take;
     let rev = commands::rev::Rev;
+    let filter = commands::filter::Filter;
+    let into = commands::into::IntoType;
+    let describe = commands::describe::Describe;
+    let open = commands::open::Open;
+    let from = commands::from::FromFormat;
+    let to = commands::to::ToFormat;
+    let save = commands::save::Save;
+    let select = commands::select::Select;
+    let reject = commands::reject::Reject;
+    let get = commands::get::Get;
+    let rename = commands::rename::Rename;
+    let insert = commands::insert::Insert;
+    let update = commands::update::Update;
+    let group_by = commands::group_by::GroupBy;
+    let each = commands::each::Each;
+    let count = commands::count::Count;
+    let length = commands::count::Count;
+    let uniq = commands::uniq::Uniq;
+    let uniq_by = commands::uniq::UniqBy;
+    let math = commands::math::Math;
+    let first = commands::first::First;
+    let last = commands::last::Last;
+    let skip = commands::skip::Skip;
+    let nth = commands::nth::Nth;
+    let range = commands::range::Range;
+    let join = commands::join::Join;
+    let merge = commands::merge::Merge;
+    let append = commands::append::Append;
+    let prepend = commands::append::Prepend;
+    let zip = commands::zip::Zip;
+    let transpose = commands::transpose::Transpose;
+    let string = commands::strings::Str;
+    let split = commands::split::Split;
+    let parse = commands::parse::Parse;
+    let kill = commands::kill::Kill;
+    let mkdir = commands::mkdir::Mkdir;
+    let cp = commands::cp::Cp;
+    let mv = commands::mv::Mv;
+    let rm = commands::rm::Rm;
+    let touch = commands::touch::Touch;
+    let trash = commands::trash::Trash;
+    let du = commands::du::Du;
+    let find = commands::find::Find;
+    let search = commands::search::Search;
+    let hash = commands::hash::Hash;
+    let stat = commands::stat::Stat;
+    let wc = commands::wc::Wc;
+    let path = commands::path::PathCommand;
+    let pushd = commands::dirs::Pushd;
+    let popd = commands::dirs::Popd;
+    let dirs = commands::dirs::Dirs;
+    let z = commands::z::Z;
+    let zi = commands::z::Zi;
 
     context.insert_commands(vec![
         ("ls", Rc::new(ls)),
@@ -42,6 +95,59 @@ pub fn run() -> Result<(), Box<dyn std::error::Error>> {
         ("sortby", Rc::new(sortby)),
         ("take", Rc::new(take)),
         ("rev", Rc::new(rev)),
+        ("filter", Rc::new(filter)),
+        ("into", Rc::new(into)),
+        ("describe", Rc::new(describe)),
+        ("open", Rc::new(open)),
+        ("from", Rc::new(from)),
+        ("to", Rc::new(to)),
+        ("save", Rc::new(save)),
+        ("select", Rc::new(select)),
+        ("reject", Rc::new(reject)),
+        ("get", Rc::new(get)),
+        ("rename", Rc::new(rename)),
+        ("insert", Rc::new(insert)),
+        ("update", Rc::new(update)),
+        ("group-by", Rc::new(group_by)),
+        ("each", Rc::new(each)),
+        ("count", Rc::new(count)),
+        ("length", Rc::new(length)),
+        ("uniq", Rc::new(uniq)),
+        ("uniq-by", Rc::new(uniq_by)),
+        ("math", Rc::new(math)),
+        ("first", Rc::new(first)),
+        ("last", Rc::new(last)),
+        ("skip", Rc::new(skip)),
+        ("nth", Rc::new(nth)),
+        ("range", Rc::new(range)),
+        ("join", Rc::new(join)),
+        ("merge", Rc::new(merge)),
+        ("append", Rc::new(append)),
+        ("prepend", Rc::new(prepend)),
+        ("zip", Rc::new(zip)),
+        ("transpose", Rc::new(transpose)),
+        ("str", Rc::new(string)),
+        ("split", Rc::new(split)),
+        ("parse", Rc::new(parse)),
+        ("kill", Rc::new(kill)),
+        ("mkdir", Rc::new(mkdir)),
+        ("cp", Rc::new(cp)),
+        ("mv", Rc::new(mv)),
+        ("rm", Rc::new(rm)),
+        ("touch", Rc::new(touch)),
+        ("trash", Rc::new(trash)),
+        ("du", Rc::new(du)),
+        ("find", Rc::new(find)),
+        ("search", Rc::new(search)),
+        ("hash", Rc::new(hash)),
+        ("stat", Rc::new(stat)),
+        ("wc", Rc::new(wc)),
+        ("path", Rc::new(path)),
+        ("pushd", Rc::new(pushd)),
+        ("popd", Rc::new(popd)),
+        ("dirs", Rc::new(dirs)),
+        ("z", Rc::new(z)),
+        ("zi", Rc::new(zi)),
     ]);
     let context = context;
 
@@ -58,6 +164,7 @@ pub fn run() -> Result<(), Box<dyn std::error::Error>> {
         hinter: HistoryHinter {},
         context: context.clone(),
         path_checker: PathChecker {},
+        directories: DirectoryCache::default(),
     };
     rl.set_helper(Some(h));
 
@@ -106,6 +213,10 @@ pub fn run() -> Result<(), Box<dyn std::error::Error>> {
             prompt_char.red().bold()
         ));
 
+        if let Some(helper) = rl.helper() {
+            helper.directories.clear();
+        }
+        interrupt::reset();
         match process_readline(&context, readline) {
             Ok(line_res) => match line_res {
                 LineResult::Success(val) => {
@@ -144,47 +255,9 @@ fn process_readline(
             "exit" => Ok(LineResult::Break),
             "" => Ok(LineResult::Success(Value::none())),
             _ => {
-                let parsed_pipeline = parselex::parser::parse(&line);
-                let command_list = build_pipeline(ctx, &parsed_pipeline);
-
-                let mut pipeline_iter = command_list.into_iter().peekable();
-
-                let mut stream = RushStream::None;
-
-                let final_result = loop {
-                    let (curr, next) = (pipeline_iter.next(), pipeline_iter.peek());
-
-                    stream = match (curr, next) {
-                        (Some(final_command), None) => match final_command {
-                            CommandType::Internal(internal) => {
-                                let result = internal.run(ctx, stream)?;
-                                break result;
-                            }
-                            CommandType::External(external) => {
-                                let result = external.run(stream, Stdio::inherit());
-                                let mut result = match result {
-                                    Ok(child) => child,
-                                    Err(_) => {
-                                        return Err(ShellError::new("rush : command not found"))
-                                    }
-                                };
-                                result.wait()?;
-                                break Value::none();
-                            }
-                        },
-                        (Some(curr_command), Some(_)) => match curr_command {
-                            CommandType::Internal(internal) => {
-                                let result = internal.run(ctx, stream)?;
-                                RushStream::Internal(result)
-                            }
-                            CommandType::External(external) => {
-                                let result = external.run(stream, Stdio::piped())?;
-                                RushStream::External(result.stdout.unwrap().into())
-                            }
-                        },
-                        (_, _) => return Ok(LineResult::Error("Not yet implemented".to_string())),
-                    }
-                };
+                let parsed_pipeline =
+                    parselex::parser::parse(&line, &|name| ctx.is_external(name))?;
+                let final_result = eval::run_pipeline(ctx, &parsed_pipeline, &Scope::default())?;
                 Ok(LineResult::Success(final_result))
             }
         },
@@ -193,30 +266,3 @@ fn process_readline(
         Err(err) => Ok(LineResult::Fatal(err.to_string())),
     }
 }
-
-fn build_pipeline(ctx: &Context, parsed_pipeline: &ParsedPipeline) -> Vec<CommandType> {
-    let commands = &parsed_pipeline.commands;
-
-    commands
-        .iter()
-        .map(|command| parsed_to_command(ctx, command))
-        .collect()
-}
-
-fn parsed_to_command(ctx: &Context, parsed_command: &ParsedCommand) -> CommandType {
-    let name = &parsed_command.name;
-    let args = &parsed_command.args;
-
-    if let Some(command) = ctx.valid_commands.get(name) {
-        let command = command.clone();
-        let args: Vec<Value> = args.iter().map(Value::from).collect();
-
-        let internal_command = InternalCommand::new(command, args);
-        CommandType::Internal(internal_command)
-    } else {
-        let name = name.to_string();
-        let args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
-        let external_command = ExternalCommand::new(name, args);
-        CommandType::External(external_command)
-    }
-}
@@ -5,21 +5,73 @@ use std::{
 
 use derive_new::new;
 
+pub mod append;
 pub mod cd;
+pub mod count;
+pub mod cp;
+pub mod describe;
+pub mod dirs;
+pub mod du;
+pub mod each;
+pub mod fileops;
+pub mod filter;
+pub mod find;
+pub mod first;
+pub mod from;
+pub mod get;
+pub mod group_by;
+pub mod hash;
+pub mod insert;
+pub mod into;
+pub mod join;
+pub mod kill;
+pub mod last;
 pub mod ls;
+pub mod math;
+pub mod merge;
+pub mod mkdir;
+pub mod mv;
+pub mod nth;
+pub mod open;
+pub mod parse;
+pub mod path;
 pub mod ps;
+pub mod range;
+pub mod reject;
+pub mod rename;
 pub mod rev;
+pub mod rm;
+pub mod save;
+pub mod search;
+pub mod select;
+pub mod skip;
 pub mod sortby;
+pub mod split;
+pub mod stat;
+pub mod strings;
 pub mod take;
+pub mod to;
+pub mod touch;
+pub mod transpose;
+pub mod trash;
+pub mod uniq;
+pub mod update;
+pub mod wc;
+pub mod z;
+pub mod zip;
 
 use crate::{
-    context::Context, environment::Environment, error::ShellError, stream::RushStream,
-    types::primary::Value,
+    context::Context,
+    environment::Environment,
+    error::ShellError,
+    stream::RushStream,
+    types::{datadict::DataDict, primary::Value},
 };
 
 pub enum CommandType {
     Internal(InternalCommand),
     External(ExternalCommand),
+    Literal(Value),
 }
 
 #[derive(new)]
@@ -31,7 +83,12 @@ pub struct InternalCommand {
 impl InternalCommand {
     pub fn run(self, ctx: &Context, instream: RushStream) -> Result<Value, ShellError> {
         let command = self.command;
-        let args = Args::new(ctx.env.clone(), self.args, instream);
+        // piped ranges are expanded here, so commands only ever see lists
+        let instream = match instream {
+            RushStream::Internal(value) => RushStream::Internal(value.expand_range()?),
+            other => other,
+        };
+        let args = Args::new(ctx.env.clone(), self.args, instream, ctx);
         command.run(args)
     }
 }
@@ -66,13 +123,70 @@ impl ExternalCommand {
     }
 }
 
-#[derive(Debug, new)]
-pub struct Args {
+#[derive(new)]
+pub struct Args<'a> {
     pub env: Rc<Environment>,
     pub args: Vec<Value>,
     pub instream: RushStream,
+    pub ctx: &'a Context,
 }
 
 pub trait Command {
     fn run(&self, args: Args) -> Result<Value, ShellError>;
 }
+
+pub fn list_input(command: &str, instream: RushStream) -> Result<Vec<Value>, ShellError> {
+    match instream {
+        RushStream::Internal(Value::List(list)) => Ok(list),
+        RushStream::External(_) => Err(ShellError::new("external streams not supported yet")),
+        _ => Err(ShellError::new(format!("{} expects a list", command))),
+    }
+}
+
+/// Item counts for first, last and skip, defaulting to one
+pub fn count_arg(command: &str, arg: Option<&Value>) -> Result<usize, ShellError> {
+    let count = match arg {
+        Some(count) => count.to_int()?,
+        None => return Ok(1),
+    };
+    usize::try_from(count)
+        .map_err(|_| ShellError::new(format!("{} expects a positive number", command)))
+}
+
+/// Applies `f` to every row of a table, or to a single record
+pub fn map_records(
+    command: &str,
+    instream: RushStream,
+    mut f: impl FnMut(DataDict) -> Result<DataDict, ShellError>,
+) -> Result<Value, ShellError> {
+    match instream {
+        RushStream::Internal(Value::List(rows)) => rows
+            .into_iter()
+            .map(|row| match row {
+                Value::Object(dict) => f(dict).map(Value::Object),
+                other => Err(ShellError::new(format!(
+                    "{} expects a table, found a list of {}",
+                    command,
+                    other.type_name()
+                ))),
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map(Value::list),
+        RushStream::Internal(Value::Object(dict)) => f(dict).map(Value::Object),
+        RushStream::External(_) => Err(ShellError::new("external streams not supported yet")),
+        _ => Err(ShellError::new(format!("{} expects a table", command))),
+    }
+}
+
+/// Evaluates `line` with `command` registered as `name`, for command tests
+#[cfg(test)]
+pub fn eval_with(
+    name: &str,
+    command: impl Command + 'static,
+    line: &str,
+) -> Result<Value, ShellError> {
+    let mut ctx = Context::default();
+    ctx.insert_commands(vec![(name, Rc::new(command) as Rc<dyn Command>)]);
+    let pipeline = crate::parselex::parser::parse(line, &|_| false)?;
+    crate::eval::run_pipeline(&ctx, &pipeline, &crate::eval::Scope::default())
+}
@@ -0,0 +1,51 @@
+use crate::{error::ShellError, stream::RushStream, types::primary::Value};
+
+use super::{Args, Command};
+
+#[derive(Debug)]
+pub struct Append;
+
+impl Command for Append {
+    fn run(&self, args: Args) -> Result<Value, ShellError> {
+        let (mut items, extra) = inputs("append", args)?;
+        items.extend(extra);
+        Ok(Value::list(items))
+    }
+}
+
+#[derive(Debug)]
+pub struct Prepend;
+
+impl Command for Prepend {
+    fn run(&self, args: Args) -> Result<Value, ShellError> {
+        let (items, mut extra) = inputs("prepend", args)?;
+        extra.extend(items);
+        Ok(Value::list(extra))
+    }
+}
+
+// A list argument adds each of its items, anything else is added as one item
+fn inputs(command: &str, args: Args) -> Result<(Vec<Value>, Vec<Value>), ShellError> {
+    let extra = match args
+        .args
+        .into_iter()
+        .next()
+        .map(Value::expand_range)
+        .transpose()?
+    {
+        Some(Value::List(items)) => items,
+        Some(item) => vec![item],
+        None => return Err(ShellError::new(format!("{} expects a value", command))),
+    };
+
+    let items = match args.instream {
+        RushStream::Internal(Value::List(items)) => items,
+        RushStream::Internal(item) => vec![item],
+        RushStream::External(_) => {
+            return Err(ShellError::new("external streams not supported yet"))
+        }
+        RushStream::None => vec![],
+    };
+
+    Ok((items, extra))
+}
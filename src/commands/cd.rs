@@ -1,7 +1,10 @@
-use std::path::PathBuf;
-
 use super::{Args, Command};
-use crate::{error::ShellError, types::primary::Value};
+use crate::{
+    error::ShellError,
+    frecency::record_visit,
+    paths::{normalize, resolve_dir},
+    types::primary::Value,
+};
 
 pub struct Cd;
 
@@ -15,22 +18,20 @@ impl Command for Cd {
             None => Err(ShellError::new("cd : Could not find home path".to_string())),
         };
 
-        let new_path = if args.args.is_empty() {
-            home_path?
-        } else {
-            let path_arg = &args.args[0].to_string();
-            let input_path = PathBuf::from(path_arg);
-
-            if input_path.is_absolute() || input_path.starts_with("~") {
-                let expanded = format!("{}", shellexpand::tilde(path_arg));
-                PathBuf::from(expanded)
-            } else {
-                cwd.join(input_path)
-            }
+        let new_path = match args.args.first().map(|arg| arg.to_string()) {
+            None => home_path?,
+            // cd - goes back to where the last cd came from
+            Some(path_arg) if path_arg == "-" => env
+                .previous_dir()
+                .ok_or_else(|| ShellError::new("cd : no previous directory"))?,
+            Some(path_arg) => resolve_dir(&cwd, &path_arg),
         };
 
         match env.set_cwd(&new_path) {
-            Ok(_) => Ok(Value::string(new_path.to_string_lossy())),
+            Ok(_) => {
+                record_visit(&env.cwd());
+                Ok(Value::string(normalize(&new_path).to_string_lossy()))
+            }
             Err(_) => Err(ShellError::new("cd : no such directory".to_string())),
         }
     }
@@ -0,0 +1,32 @@
+use crate::{
+    error::ShellError,
+    stream::RushStream,
+    types::primary::{Primitive, Value},
+};
+
+use super::{Args, Command};
+
+#[derive(Debug)]
+pub struct Count;
+
+impl Command for Count {
+    fn run(&self, args: Args) -> Result<Value, ShellError> {
+        let count = match args.instream {
+            RushStream::Internal(Value::List(list)) => list.len(),
+            RushStream::Internal(Value::Object(dict)) => dict.iter().count(),
+            RushStream::Internal(Value::Primitive(Primitive::String(s))) => s.chars().count(),
+            RushStream::External(_) => {
+                return Err(ShellError::new("external streams not supported yet"))
+            }
+            RushStream::None => 0,
+            RushStream::Internal(value) => {
+                return Err(ShellError::new(format!(
+                    "cannot count a {}",
+                    value.type_name()
+                )))
+            }
+        };
+
+        Ok(Value::int(count as i64))
+    }
+}
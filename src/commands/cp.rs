@@ -0,0 +1,74 @@
+use std::path::PathBuf;
+
+use crate::{error::ShellError, paths::expand_path, stream::RushStream, types::primary::Value};
+
+use super::{
+    fileops::{change_row, copy_path, destination_for, expand_paths, piped_paths, FileArgs},
+    Args, Command,
+};
+
+#[derive(Debug)]
+pub struct Cp;
+
+impl Command for Cp {
+    fn run(&self, args: Args) -> Result<Value, ShellError> {
+        let mut file_args =
+            FileArgs::parse("cp", &args.args, &[("recursive", 'r'), ("dry-run", 'n')])?;
+        let recursive = file_args.has("recursive");
+        let dry_run = file_args.has("dry-run");
+
+        let (sources, destination) = sources_and_destination("cp", &mut file_args, args.instream)?;
+
+        let mut changes = vec![];
+        for source in sources {
+            let target = destination_for(&source, &destination);
+            let result = match dry_run {
+                true => Ok(()),
+                false => copy_path(&source, &target, recursive),
+            };
+            changes.push(change_row(
+                "copied",
+                &source,
+                Some(&target),
+                result,
+                dry_run,
+            ));
+        }
+
+        Ok(Value::list(changes))
+    }
+}
+
+/// The last path is the destination, sources are the other paths or piped rows
+pub fn sources_and_destination(
+    command: &str,
+    file_args: &mut FileArgs,
+    instream: RushStream,
+) -> Result<(Vec<PathBuf>, PathBuf), ShellError> {
+    let destination = match file_args.paths.pop() {
+        Some(destination) => expand_path(&destination),
+        None => {
+            return Err(ShellError::new(format!(
+                "{} expects a destination, e.g. {} a.txt b.txt",
+                command, command
+            )))
+        }
+    };
+
+    let mut sources = piped_paths(command, instream)?;
+    sources.extend(expand_paths(command, &file_args.paths)?);
+    if sources.is_empty() {
+        return Err(ShellError::new(format!("{} expects a source", command)));
+    }
+
+    // several sources can only go into a directory
+    if sources.len() > 1 && !destination.is_dir() {
+        return Err(ShellError::new(format!(
+            "{} : {} is not a directory",
+            command,
+            destination.display()
+        )));
+    }
+
+    Ok((sources, destination))
+}
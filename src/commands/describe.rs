@@ -0,0 +1,68 @@
+use crate::{
+    error::ShellError,
+    stream::RushStream,
+    types::{datadict::DataDict, primary::Value},
+};
+
+use super::{Args, Command};
+
+#[derive(Debug)]
+pub struct Describe;
+
+impl Command for Describe {
+    fn run(&self, args: Args) -> Result<Value, ShellError> {
+        // describe 5 works as well as 5 | describe
+        if let Some(value) = args.args.first() {
+            return Ok(Value::string(describe(value)));
+        }
+
+        match args.instream {
+            RushStream::Internal(value) => Ok(Value::string(describe(&value))),
+            RushStream::External(_) => Err(ShellError::new("external streams not supported yet")),
+            RushStream::None => Ok(Value::string("nothing")),
+        }
+    }
+}
+
+fn describe(value: &Value) -> String {
+    match value {
+        Value::Object(dict) => format!("record<{}>", describe_columns(dict)),
+        Value::List(items) => describe_list(items),
+        Value::Primitive(primitive) => primitive.type_name().to_string(),
+        Value::Lambda(_) => "lambda".to_string(),
+    }
+}
+
+// Lists of records sharing the same columns are tables
+fn describe_list(items: &[Value]) -> String {
+    let columns = |value: &Value| match value {
+        Value::Object(dict) => Some(
+            dict.data_descriptors()
+                .into_iter()
+                .map(|desc| desc.name)
+                .collect::<Vec<_>>(),
+        ),
+        _ => None,
+    };
+
+    if let Some(Value::Object(first)) = items.first() {
+        let first_columns = columns(&items[0]);
+        if items.iter().all(|item| columns(item) == first_columns) {
+            return format!("table<{}>", describe_columns(first));
+        }
+    }
+
+    let mut types = items.iter().map(describe);
+    match types.next() {
+        Some(first) if types.all(|other| other == first) => format!("list<{}>", first),
+        _ => "list<any>".to_string(),
+    }
+}
+
+fn describe_columns(dict: &DataDict) -> String {
+    dict.data_descriptors()
+        .iter()
+        .map(|desc| format!("{}: {}", desc.name, describe(dict.get_data(desc))))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
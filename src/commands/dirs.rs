@@ -0,0 +1,78 @@
+use crate::{
+    environment::Environment,
+    error::ShellError,
+    paths::resolve_path,
+    types::{datadict::DataDict, primary::Value},
+};
+
+use super::{Args, Command};
+
+#[derive(Debug)]
+pub struct Pushd;
+
+#[derive(Debug)]
+pub struct Popd;
+
+#[derive(Debug)]
+pub struct Dirs;
+
+impl Command for Pushd {
+    fn run(&self, args: Args) -> Result<Value, ShellError> {
+        let env = args.env;
+        let cwd = env.cwd();
+
+        // pushd on its own swaps the working directory with the top of the stack
+        let target = match args.args.first() {
+            Some(path_arg) => resolve_path(&cwd, &path_arg.to_string()),
+            None => env
+                .pop_dir()
+                .ok_or_else(|| ShellError::new("pushd : no other directory"))?,
+        };
+
+        if env.set_cwd(&target).is_err() {
+            if args.args.is_empty() {
+                env.push_dir(target);
+            }
+            return Err(ShellError::new("pushd : no such directory"));
+        }
+        env.push_dir(cwd);
+
+        Ok(dirs_table(&env))
+    }
+}
+
+impl Command for Popd {
+    fn run(&self, args: Args) -> Result<Value, ShellError> {
+        let env = args.env;
+        let target = env
+            .pop_dir()
+            .ok_or_else(|| ShellError::new("popd : directory stack empty"))?;
+
+        // a directory removed since it was pushed is dropped from the stack
+        env.set_cwd(&target)
+            .map_err(|err| ShellError::new(format!("popd : {} : {}", target.display(), err)))?;
+
+        Ok(dirs_table(&env))
+    }
+}
+
+impl Command for Dirs {
+    fn run(&self, args: Args) -> Result<Value, ShellError> {
+        Ok(dirs_table(&args.env))
+    }
+}
+
+// the working directory is index 0, followed by the stack from the top
+fn dirs_table(env: &Environment) -> Value {
+    let dirs = std::iter::once(env.cwd()).chain(env.dir_stack());
+    let rows = dirs
+        .enumerate()
+        .map(|(index, dir)| {
+            let mut dict = DataDict::default();
+            dict.insert("index", Value::int(index as i64));
+            dict.insert("path", Value::path(dir));
+            Value::object(dict)
+        })
+        .collect::<Vec<_>>();
+    Value::list(rows)
+}
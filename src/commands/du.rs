@@ -0,0 +1,140 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    error::ShellError,
+    interrupt,
+    paths::expand_path,
+    types::{datadict::DataDict, primary::Value},
+};
+
+use super::{Args, Command};
+
+#[derive(Debug)]
+pub struct Du;
+
+/// Cumulative usage of one directory
+struct Usage {
+    path: PathBuf,
+    size: u64,
+    files: u64,
+}
+
+impl Command for Du {
+    fn run(&self, args: Args) -> Result<Value, ShellError> {
+        let mut depth = None;
+        let mut paths = vec![];
+
+        // du src --depth 1
+        let mut params = args.args.iter();
+        while let Some(arg) = params.next() {
+            match arg.to_string().as_str() {
+                "--depth" | "-d" => {
+                    let max_depth = params
+                        .next()
+                        .ok_or_else(|| ShellError::new("du : --depth expects a number"))?
+                        .to_int()?;
+                    let max_depth = usize::try_from(max_depth)
+                        .map_err(|_| ShellError::new("du : --depth expects a positive number"))?;
+                    depth = Some(max_depth);
+                }
+                path => paths.push(path.to_string()),
+            }
+        }
+        if paths.is_empty() {
+            paths.push(".".to_string());
+        }
+
+        let mut rows = vec![];
+        for path in &paths {
+            let expanded = expand_path(path);
+            fs::symlink_metadata(&expanded)
+                .map_err(|err| ShellError::new(format!("du : {} : {}", path, err)))?;
+            disk_usage(&expanded, Path::new(path), 0, depth, &mut rows)?;
+        }
+
+        let rows = rows
+            .into_iter()
+            .map(|usage| {
+                let mut dict = DataDict::default();
+                dict.insert("path", Value::path(usage.path));
+                dict.insert("size", Value::size(usage.size));
+                dict.insert("files", Value::int(usage.files as i64));
+                Value::object(dict)
+            })
+            .collect::<Vec<_>>();
+
+        Ok(Value::list(rows))
+    }
+}
+
+// Rows are pushed before their subdirectories and filled in once those are summed,
+// directories deeper than `max_depth` still count towards their parents
+fn disk_usage(
+    path: &Path,
+    display: &Path,
+    depth: usize,
+    max_depth: Option<usize>,
+    rows: &mut Vec<Usage>,
+) -> Result<(u64, u64), ShellError> {
+    if interrupt::interrupted() {
+        return Err(ShellError::new("du : interrupted"));
+    }
+
+    // symlinks are counted themselves, never followed
+    let metadata = match fs::symlink_metadata(path) {
+        Ok(metadata) => metadata,
+        Err(_) => return Ok((0, 0)),
+    };
+    let listed = !matches!(max_depth, Some(max_depth) if depth > max_depth);
+
+    if !metadata.is_dir() {
+        // files are only listed when given directly
+        if depth == 0 {
+            rows.push(Usage {
+                path: display.to_path_buf(),
+                size: metadata.len(),
+                files: 1,
+            });
+        }
+        return Ok((metadata.len(), 1));
+    }
+
+    let row = rows.len();
+    if listed {
+        rows.push(Usage {
+            path: display.to_path_buf(),
+            size: 0,
+            files: 0,
+        });
+    }
+
+    // unreadable directories count as empty
+    let mut children = match fs::read_dir(path) {
+        Ok(entries) => entries.flatten().collect::<Vec<_>>(),
+        Err(_) => vec![],
+    };
+    children.sort_by_key(|child| child.file_name());
+
+    let (mut size, mut files) = (metadata.len(), 0);
+    for child in children {
+        let name = child.file_name();
+        let (child_size, child_files) = disk_usage(
+            &child.path(),
+            &display.join(&name),
+            depth + 1,
+            max_depth,
+            rows,
+        )?;
+        size += child_size;
+        files += child_files;
+    }
+
+    if listed {
+        rows[row].size = size;
+        rows[row].files = files;
+    }
+    Ok((size, files))
+}
@@ -0,0 +1,30 @@
+use crate::{error::ShellError, stream::RushStream, types::primary::Value};
+
+use super::{Args, Command};
+
+#[derive(Debug)]
+pub struct Each;
+
+impl Command for Each {
+    fn run(&self, args: Args) -> Result<Value, ShellError> {
+        let lambda = match args.args.first() {
+            Some(Value::Lambda(lambda)) => lambda,
+            _ => {
+                return Err(ShellError::new(
+                    "each expects a lambda, e.g. each f -> f.size",
+                ))
+            }
+        };
+
+        match args.instream {
+            RushStream::Internal(Value::List(list)) => list
+                .into_iter()
+                .map(|item| lambda.call(args.ctx, item))
+                .collect::<Result<Vec<_>, _>>()
+                .map(Value::list),
+            RushStream::Internal(value) => lambda.call(args.ctx, value),
+            RushStream::External(_) => Err(ShellError::new("external streams not supported yet")),
+            RushStream::None => Err(ShellError::new("each expects an input")),
+        }
+    }
+}
@@ -0,0 +1,208 @@
+use std::{
+    collections::HashSet,
+    env, fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    error::ShellError,
+    paths::{expand_path, normalize},
+    stream::RushStream,
+    types::{datadict::DataDict, primary::Value},
+};
+
+use super::strings::as_text;
+
+/// Flags given to a file command, by long name
+pub struct FileArgs {
+    flags: HashSet<&'static str>,
+    pub paths: Vec<String>,
+}
+
+impl FileArgs {
+    /// Splits flags from paths, `known` pairs long names with their short form,
+    /// short flags can be combined as in rm -rf
+    pub fn parse(
+        command: &str,
+        args: &[Value],
+        known: &[(&'static str, char)],
+    ) -> Result<FileArgs, ShellError> {
+        let mut flags = HashSet::new();
+        let mut paths = vec![];
+        let unknown = |flag: &str| ShellError::new(format!("{} : unknown flag {}", command, flag));
+
+        for arg in args {
+            let arg = arg.to_string();
+            if let Some(long) = arg.strip_prefix("--") {
+                let (name, _) = known
+                    .iter()
+                    .find(|(name, _)| *name == long)
+                    .ok_or_else(|| unknown(&arg))?;
+                flags.insert(*name);
+            } else if arg.starts_with('-') && arg.len() > 1 {
+                for short in arg.chars().skip(1) {
+                    let (name, _) = known
+                        .iter()
+                        .find(|(_, flag)| *flag == short)
+                        .ok_or_else(|| unknown(&format!("-{}", short)))?;
+                    flags.insert(*name);
+                }
+            } else {
+                paths.push(arg);
+            }
+        }
+
+        Ok(FileArgs { flags, paths })
+    }
+
+    pub fn has(&self, flag: &str) -> bool {
+        self.flags.contains(flag)
+    }
+}
+
+/// Expands `~` and globs, a literal path is kept even when it does not exist
+pub fn expand_paths(command: &str, patterns: &[String]) -> Result<Vec<PathBuf>, ShellError> {
+    let mut paths = vec![];
+    for pattern in patterns {
+        let expanded = expand_path(pattern).to_string_lossy().into_owned();
+        if !expanded.contains(['*', '?', '[']) {
+            paths.push(PathBuf::from(expanded));
+            continue;
+        }
+
+        let matches = glob::glob(&expanded)
+            .map_err(|err| ShellError::new(format!("{} : {} : {}", command, pattern, err)))?;
+        let before = paths.len();
+        paths.extend(matches.flatten());
+        if paths.len() == before {
+            return Err(ShellError::new(format!(
+                "{} : no matches for {}",
+                command, pattern
+            )));
+        }
+    }
+    Ok(paths)
+}
+
+/// Paths piped in as ls rows, or as a list of strings
+pub fn piped_paths(command: &str, instream: RushStream) -> Result<Vec<PathBuf>, ShellError> {
+    let items = match instream {
+        RushStream::Internal(Value::List(items)) => items,
+        RushStream::Internal(value) => vec![value],
+        RushStream::External(_) => {
+            return Err(ShellError::new("external streams not supported yet"))
+        }
+        RushStream::None => return Ok(vec![]),
+    };
+
+    items
+        .iter()
+        .map(|item| {
+            let name = match item {
+                Value::Object(dict) => dict.get("name").and_then(as_text),
+                other => as_text(other),
+            };
+            name.map(|name| expand_path(&name)).ok_or_else(|| {
+                ShellError::new(format!(
+                    "{} expects paths or rows with a name column",
+                    command
+                ))
+            })
+        })
+        .collect()
+}
+
+/// Where a copied or moved path ends up, directories receive it under its own name
+pub fn destination_for(source: &Path, destination: &Path) -> PathBuf {
+    match (destination.is_dir(), source.file_name()) {
+        (true, Some(name)) => destination.join(name),
+        _ => destination.to_path_buf(),
+    }
+}
+
+/// Copies files, symlinks and, when `recursive`, whole directories
+pub fn copy_path(source: &Path, destination: &Path, recursive: bool) -> Result<(), ShellError> {
+    let metadata = fs::symlink_metadata(source)?;
+
+    if metadata.file_type().is_symlink() {
+        std::os::unix::fs::symlink(fs::read_link(source)?, destination)?;
+    } else if metadata.is_dir() {
+        if !recursive {
+            return Err(ShellError::new("is a directory, use -r to copy it"));
+        }
+        // cp -r . sub and cp -r src ./src/x would otherwise copy their own output forever
+        if resolve_destination(destination)?.starts_with(source.canonicalize()?) {
+            return Err(ShellError::new("cannot copy a directory into itself"));
+        }
+        fs::create_dir_all(destination)?;
+        for entry in fs::read_dir(source)? {
+            let entry = entry?;
+            copy_path(&entry.path(), &destination.join(entry.file_name()), true)?;
+        }
+    } else {
+        fs::copy(source, destination)?;
+    }
+
+    Ok(())
+}
+
+// A destination that may not exist yet, resolved through its closest existing ancestor
+fn resolve_destination(destination: &Path) -> io::Result<PathBuf> {
+    let absolute = normalize(&env::current_dir()?.join(destination));
+    let mut existing = absolute.as_path();
+    let mut missing = vec![];
+    while !existing.exists() {
+        match (existing.file_name(), existing.parent()) {
+            (Some(name), Some(parent)) => {
+                missing.push(name);
+                existing = parent;
+            }
+            _ => break,
+        }
+    }
+
+    let mut resolved = existing.canonicalize()?;
+    resolved.extend(missing.into_iter().rev());
+    Ok(resolved)
+}
+
+/// Renames, falling back to copy and remove across filesystems
+pub fn move_path(source: &Path, target: &Path) -> Result<(), ShellError> {
+    match fs::rename(source, target) {
+        Err(err) if err.raw_os_error() == Some(libc::EXDEV) => {
+            copy_path(source, target, true)?;
+            match fs::symlink_metadata(source)?.is_dir() {
+                true => fs::remove_dir_all(source)?,
+                false => fs::remove_file(source)?,
+            }
+            Ok(())
+        }
+        result => Ok(result?),
+    }
+}
+
+/// Row describing one change, failures are reported per file instead of ending the batch
+pub fn change_row(
+    action: &str,
+    path: &Path,
+    destination: Option<&Path>,
+    result: Result<(), ShellError>,
+    dry_run: bool,
+) -> Value {
+    let mut dict = DataDict::default();
+    dict.insert("action", Value::string(action));
+    dict.insert("path", Value::path(path));
+    if let Some(destination) = destination {
+        dict.insert("destination", Value::path(destination));
+    }
+
+    let (status, error) = match (dry_run, result) {
+        (true, _) => ("dry run", "".to_string()),
+        (false, Ok(())) => ("ok", "".to_string()),
+        (false, Err(err)) => ("failed", err.to_string()),
+    };
+    dict.insert("status", Value::string(status));
+    dict.insert("error", Value::string(error));
+
+    Value::object(dict)
+}
@@ -0,0 +1,37 @@
+use crate::{error::ShellError, stream::RushStream, types::primary::Value};
+
+use super::{Args, Command};
+
+#[derive(Debug)]
+pub struct Filter;
+
+impl Command for Filter {
+    fn run(&self, args: Args) -> Result<Value, ShellError> {
+        let lambda = match args.args.first() {
+            Some(Value::Lambda(lambda)) => lambda,
+            _ => {
+                return Err(ShellError::new(
+                    "filter expects a lambda, e.g. f -> f.size > 50",
+                ))
+            }
+        };
+
+        let objects = match args.instream {
+            RushStream::Internal(Value::List(list)) => list,
+            RushStream::External(_) => {
+                return Err(ShellError::new("external streams not supported yet"))
+            }
+            _ => return Err(ShellError::new("filter expects a list of objects")),
+        };
+
+        let mut filtered = vec![];
+
+        for object in objects {
+            if lambda.call(args.ctx, object.clone())?.to_bool()? {
+                filtered.push(object);
+            }
+        }
+
+        Ok(Value::list(filtered))
+    }
+}
@@ -0,0 +1,246 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::{mpsc, Arc},
+    time::{Duration, SystemTime},
+};
+
+use ignore::{WalkBuilder, WalkState};
+use regex::Regex;
+
+use crate::{
+    error::ShellError,
+    interrupt,
+    paths::expand_path,
+    types::{
+        direntry::direntry_dict,
+        primary::{Primitive, Value},
+    },
+};
+
+use super::{
+    into::{to_duration, to_filesize},
+    Args, Command,
+};
+
+#[derive(Debug)]
+pub struct Find;
+
+#[derive(Default)]
+struct Predicates {
+    name: Option<glob::Pattern>,
+    regex: Option<Regex>,
+    kind: Option<&'static str>,
+    min_size: Option<u64>,
+    max_size: Option<u64>,
+    newer_than: Option<SystemTime>,
+    older_than: Option<SystemTime>,
+}
+
+impl Predicates {
+    fn matches(&self, path: &Path, entry: &ignore::DirEntry) -> bool {
+        if let Some(pattern) = &self.name {
+            if !pattern.matches(&entry.file_name().to_string_lossy()) {
+                return false;
+            }
+        }
+        if let Some(regex) = &self.regex {
+            if !regex.is_match(&path.to_string_lossy()) {
+                return false;
+            }
+        }
+        if let Some(kind) = self.kind {
+            let matches = entry.file_type().is_some_and(|file_type| match kind {
+                "dir" => file_type.is_dir(),
+                "symlink" => file_type.is_symlink(),
+                _ => file_type.is_file(),
+            });
+            if !matches {
+                return false;
+            }
+        }
+
+        // metadata is only read when a predicate needs it
+        let needs_metadata = self.min_size.is_some()
+            || self.max_size.is_some()
+            || self.newer_than.is_some()
+            || self.older_than.is_some();
+        if !needs_metadata {
+            return true;
+        }
+        let metadata = match entry.metadata() {
+            Ok(metadata) => metadata,
+            Err(_) => return false,
+        };
+        let modified = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+
+        let size = metadata.len();
+        let too_small = matches!(self.min_size, Some(min) if size < min);
+        let too_large = matches!(self.max_size, Some(max) if size > max);
+        let too_old = matches!(self.newer_than, Some(cutoff) if modified < cutoff);
+        let too_new = matches!(self.older_than, Some(cutoff) if modified > cutoff);
+        !(too_small || too_large || too_old || too_new)
+    }
+}
+
+impl Command for Find {
+    fn run(&self, args: Args) -> Result<Value, ShellError> {
+        let mut predicates = Predicates::default();
+        let mut roots = vec![];
+        let mut gitignore = false;
+        let mut hidden = false;
+
+        // find src --name *.rs --newer 1day
+        let mut params = args.args.iter();
+        while let Some(arg) = params.next() {
+            let flag = arg.to_string();
+            if !flag.starts_with('-') || flag.len() == 1 {
+                roots.push(flag);
+                continue;
+            }
+
+            match flag.as_str() {
+                "--gitignore" | "-g" => {
+                    gitignore = true;
+                    continue;
+                }
+                "--all" | "-a" => {
+                    hidden = true;
+                    continue;
+                }
+                _ => {}
+            }
+
+            let value = params
+                .next()
+                .ok_or_else(|| ShellError::new(format!("find : {} expects a value", flag)))?;
+            match flag.as_str() {
+                "--name" | "-n" => {
+                    let pattern = glob::Pattern::new(&value.to_string())
+                        .map_err(|err| ShellError::new(format!("find : invalid glob : {}", err)))?;
+                    predicates.name = Some(pattern);
+                }
+                "--regex" | "-r" => {
+                    let regex = Regex::new(&value.to_string()).map_err(|err| {
+                        ShellError::new(format!("find : invalid regex : {}", err))
+                    })?;
+                    predicates.regex = Some(regex);
+                }
+                "--type" | "-t" => {
+                    predicates.kind = match value.to_string().as_str() {
+                        "f" | "file" => Some("file"),
+                        "d" | "dir" => Some("dir"),
+                        "l" | "symlink" => Some("symlink"),
+                        other => {
+                            return Err(ShellError::new(format!(
+                                "find : unknown type {}, expected file, dir or symlink",
+                                other
+                            )))
+                        }
+                    }
+                }
+                "--min-size" => predicates.min_size = Some(size_arg(value)?),
+                "--max-size" => predicates.max_size = Some(size_arg(value)?),
+                // modified within, or longer ago than, a duration
+                "--newer" => predicates.newer_than = Some(cutoff_arg(value)?),
+                "--older" => predicates.older_than = Some(cutoff_arg(value)?),
+                _ => return Err(ShellError::new(format!("find : unknown flag {}", flag))),
+            }
+        }
+
+        // without a root, the working directory is searched and names are relative
+        let bare = roots.is_empty();
+        if bare {
+            roots.push(".".to_string());
+        }
+        let roots: Vec<PathBuf> = roots.iter().map(|root| expand_path(root)).collect();
+
+        let mut builder = WalkBuilder::new(&roots[0]);
+        for root in &roots[1..] {
+            builder.add(root);
+        }
+        builder
+            .standard_filters(false)
+            .hidden(!hidden)
+            .git_ignore(gitignore)
+            .git_exclude(gitignore)
+            .git_global(gitignore)
+            .ignore(gitignore)
+            .parents(gitignore);
+
+        let predicates = Arc::new(predicates);
+        let (sender, receiver) = mpsc::channel();
+
+        builder.build_parallel().run(|| {
+            let sender = sender.clone();
+            let predicates = Arc::clone(&predicates);
+            Box::new(move |result| {
+                if interrupt::interrupted() {
+                    return WalkState::Quit;
+                }
+                // unreadable entries are skipped, as are the roots themselves
+                let entry = match result {
+                    Ok(entry) if entry.depth() > 0 => entry,
+                    _ => return WalkState::Continue,
+                };
+                let path = match bare {
+                    true => entry.path().strip_prefix(".").unwrap_or(entry.path()),
+                    false => entry.path(),
+                };
+                if predicates.matches(path, &entry) {
+                    let _ = sender.send((path.to_path_buf(), entry.path().to_path_buf()));
+                }
+                WalkState::Continue
+            })
+        });
+        drop(sender);
+
+        if interrupt::interrupted() {
+            return Err(ShellError::new("find : interrupted"));
+        }
+
+        // parallel walks finish in any order
+        let mut found: Vec<(PathBuf, PathBuf)> = receiver.into_iter().collect();
+        found.sort();
+
+        let mut entries = vec![];
+        for (name, path) in found {
+            // files can vanish between the walk and now
+            if let Ok(dict) = direntry_dict(&path, name, None) {
+                entries.push(Value::object(dict));
+            }
+        }
+
+        Ok(Value::list(entries))
+    }
+}
+
+fn size_arg(value: &Value) -> Result<u64, ShellError> {
+    match value {
+        Value::Primitive(primitive) => match to_filesize(primitive) {
+            Some(Primitive::Size(bytes)) => Ok(bytes),
+            _ => Err(ShellError::new(format!(
+                "find : expected a size, e.g. 10KB, found {}",
+                value
+            ))),
+        },
+        _ => Err(ShellError::new("find : expected a size, e.g. 10KB")),
+    }
+}
+
+fn cutoff_arg(value: &Value) -> Result<SystemTime, ShellError> {
+    let nanos = match value {
+        Value::Primitive(primitive) => match to_duration(primitive) {
+            Some(Primitive::Duration(nanos)) => nanos,
+            _ => 0,
+        },
+        _ => 0,
+    };
+    let age = u64::try_from(nanos)
+        .ok()
+        .filter(|nanos| *nanos > 0)
+        .ok_or_else(|| ShellError::new("find : expected a duration, e.g. 2day"))?;
+
+    Ok(SystemTime::now()
+        .checked_sub(Duration::from_nanos(age))
+        .unwrap_or(SystemTime::UNIX_EPOCH))
+}
@@ -0,0 +1,53 @@
+use crate::{error::ShellError, types::primary::Value};
+
+use super::{count_arg, list_input, Args, Command};
+
+#[derive(Debug)]
+pub struct First;
+
+impl Command for First {
+    fn run(&self, args: Args) -> Result<Value, ShellError> {
+        let mut objects = list_input("first", args.instream)?;
+
+        // without a count the item itself is returned
+        if args.args.is_empty() {
+            return match objects.is_empty() {
+                true => Err(ShellError::new("first : the list is empty")),
+                false => Ok(objects.swap_remove(0)),
+            };
+        }
+
+        let count = count_arg("first", args.args.first())?;
+        objects.truncate(count);
+        Ok(Value::list(objects))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::eval_with;
+
+    fn first(line: &str) -> Value {
+        eval_with("first", First, line).unwrap()
+    }
+
+    fn fails(line: &str) -> bool {
+        eval_with("first", First, line).is_err()
+    }
+
+    #[test]
+    fn without_a_count_the_item() {
+        assert_eq!(first("[1 2 3] | first"), Value::int(1));
+        assert_eq!(first("[{a: 1} {a: 2}] | first"), first("{a: 1}"));
+        assert!(fails("[] | first"));
+    }
+
+    #[test]
+    fn with_a_count_a_list() {
+        assert_eq!(first("[1 2 3] | first 2"), first("[1 2]"));
+        assert_eq!(first("[1 2 3] | first 1"), first("[1]"));
+        assert_eq!(first("[1 2] | first 5"), first("[1 2]"));
+        assert!(fails("[1 2] | first -1"));
+    }
+}
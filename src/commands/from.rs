@@ -0,0 +1,27 @@
+use crate::{
+    error::ShellError,
+    formats::Format,
+    stream::RushStream,
+    types::primary::{Primitive, Value},
+};
+
+use super::{Args, Command};
+
+#[derive(Debug)]
+pub struct FromFormat;
+
+impl Command for FromFormat {
+    fn run(&self, args: Args) -> Result<Value, ShellError> {
+        let format = match args.args.first() {
+            Some(name) => Format::from_name(&name.to_string())
+                .ok_or_else(|| ShellError::new(format!("from : unknown format {}", name)))?,
+            None => return Err(ShellError::new("from expects a format, e.g. from json")),
+        };
+
+        match args.instream {
+            RushStream::Internal(Value::Primitive(Primitive::String(text))) => format.parse(&text),
+            RushStream::External(_) => Err(ShellError::new("external streams not supported yet")),
+            _ => Err(ShellError::new("from expects text input")),
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use crate::{error::ShellError, stream::RushStream, types::primary::Value};
+
+use super::{Args, Command};
+
+#[derive(Debug)]
+pub struct Get;
+
+impl Command for Get {
+    fn run(&self, args: Args) -> Result<Value, ShellError> {
+        let path = match args.args.first() {
+            Some(path) => path.to_string(),
+            None => return Err(ShellError::new("get expects a column, e.g. get name")),
+        };
+
+        let mut value = match args.instream {
+            RushStream::Internal(value) => value,
+            RushStream::External(_) => {
+                return Err(ShellError::new("external streams not supported yet"))
+            }
+            RushStream::None => return Err(ShellError::new("get expects a table or record")),
+        };
+
+        // nested columns and list indices, e.g. get deps.0.name
+        for field in path.split('.') {
+            value = value.get_field(field)?;
+        }
+
+        Ok(value)
+    }
+}
@@ -0,0 +1,57 @@
+use indexmap::IndexMap;
+
+use crate::{
+    error::ShellError,
+    stream::RushStream,
+    types::{datadict::DataDict, primary::Value},
+};
+
+use super::{Args, Command};
+
+#[derive(Debug)]
+pub struct GroupBy;
+
+impl Command for GroupBy {
+    fn run(&self, args: Args) -> Result<Value, ShellError> {
+        let column = match args.args.first() {
+            Some(column) => column.to_string(),
+            None => {
+                return Err(ShellError::new(
+                    "group-by expects a column, e.g. group-by name",
+                ))
+            }
+        };
+
+        let rows = match args.instream {
+            RushStream::Internal(Value::List(list)) => list,
+            RushStream::External(_) => {
+                return Err(ShellError::new("external streams not supported yet"))
+            }
+            _ => return Err(ShellError::new("group-by expects a table")),
+        };
+
+        // groups keep the order in which their key was first seen
+        let mut groups: IndexMap<String, (Value, Vec<Value>)> = IndexMap::new();
+        for row in rows {
+            let key = row.get_field(&column)?;
+            groups
+                .entry(key.to_string())
+                .or_insert_with(|| (key, vec![]))
+                .1
+                .push(row);
+        }
+
+        let groups = groups
+            .into_values()
+            .map(|(key, items)| {
+                let mut group = DataDict::default();
+                group.insert(&column, key);
+                group.insert("count", Value::int(items.len() as i64));
+                group.insert("items", Value::list(items));
+                Value::object(group)
+            })
+            .collect::<Vec<_>>();
+
+        Ok(Value::list(groups))
+    }
+}
@@ -0,0 +1,142 @@
+use std::{
+    fs::File,
+    io::{self, Write},
+    path::Path,
+};
+
+use md5::Md5;
+use sha2::{Digest, Sha256};
+
+use crate::{
+    error::ShellError,
+    paths::expand_path,
+    stream::RushStream,
+    types::{
+        datadict::DataDict,
+        primary::{Primitive, Value},
+    },
+};
+
+use super::{fileops::expand_paths, strings::as_text, Args, Command};
+
+#[derive(Debug)]
+pub struct Hash;
+
+#[derive(Clone, Copy)]
+enum Algorithm {
+    Sha256,
+    Md5,
+}
+
+impl Algorithm {
+    fn name(&self) -> &'static str {
+        match self {
+            Algorithm::Sha256 => "sha256",
+            Algorithm::Md5 => "md5",
+        }
+    }
+
+    fn hash_bytes(&self, bytes: &[u8]) -> String {
+        match self {
+            Algorithm::Sha256 => format!("{:x}", Sha256::digest(bytes)),
+            Algorithm::Md5 => format!("{:x}", Md5::digest(bytes)),
+        }
+    }
+
+    // files are hashed as they are read, never loaded whole
+    fn hash_file(&self, path: &Path) -> Result<String, ShellError> {
+        let mut file = File::open(path)
+            .map_err(|err| ShellError::new(format!("hash : {} : {}", path.display(), err)))?;
+        match self {
+            Algorithm::Sha256 => Ok(format!("{:x}", digest_reader(Sha256::new(), &mut file)?)),
+            Algorithm::Md5 => Ok(format!("{:x}", digest_reader(Md5::new(), &mut file)?)),
+        }
+    }
+}
+
+fn digest_reader<D: Digest + Write>(
+    mut hasher: D,
+    reader: &mut impl io::Read,
+) -> Result<sha2::digest::Output<D>, ShellError> {
+    io::copy(reader, &mut hasher)?;
+    Ok(hasher.finalize())
+}
+
+impl Command for Hash {
+    fn run(&self, args: Args) -> Result<Value, ShellError> {
+        let mut params = args.args.iter().map(|arg| arg.to_string());
+
+        let algorithm = match params.next().as_deref() {
+            Some("sha256") => Algorithm::Sha256,
+            Some("md5") => Algorithm::Md5,
+            Some(other) => {
+                return Err(ShellError::new(format!(
+                    "hash : unknown algorithm {}, expected sha256 or md5",
+                    other
+                )))
+            }
+            None => {
+                return Err(ShellError::new(
+                    "hash expects an algorithm, e.g. hash sha256",
+                ))
+            }
+        };
+        let files = expand_paths("hash", &params.collect::<Vec<_>>())?;
+
+        // hash sha256 a.txt is the digest itself, several files give a table
+        if !files.is_empty() {
+            let mut rows = vec![];
+            for path in &files {
+                let digest = algorithm.hash_file(path)?;
+                if files.len() == 1 {
+                    return Ok(Value::string(digest));
+                }
+                let mut dict = DataDict::default();
+                dict.insert("path", Value::path(path));
+                dict.insert(algorithm.name(), Value::string(digest));
+                rows.push(Value::object(dict));
+            }
+            return Ok(Value::list(rows));
+        }
+
+        match args.instream {
+            RushStream::Internal(Value::List(items)) => items
+                .into_iter()
+                .map(|item| hash_value(algorithm, item))
+                .collect::<Result<Vec<_>, _>>()
+                .map(Value::list),
+            RushStream::Internal(value) => hash_value(algorithm, value),
+            RushStream::External(_) => Err(ShellError::new("external streams not supported yet")),
+            RushStream::None => Err(ShellError::new("hash expects a file or an input")),
+        }
+    }
+}
+
+// ls rows get a digest column, strings and binary data are hashed as they are
+fn hash_value(algorithm: Algorithm, value: Value) -> Result<Value, ShellError> {
+    match value {
+        Value::Object(mut dict) => {
+            let path = dict.get("name").and_then(as_text).ok_or_else(|| {
+                ShellError::new("hash expects rows with a name column, e.g. ls | hash sha256")
+            })?;
+            let path = expand_path(&path);
+            // directories have no content to hash
+            let digest = match path.is_dir() {
+                true => Value::none(),
+                false => Value::string(algorithm.hash_file(&path)?),
+            };
+            dict.insert(algorithm.name(), digest);
+            Ok(Value::Object(dict))
+        }
+        Value::Primitive(Primitive::Binary(bytes)) => {
+            Ok(Value::string(algorithm.hash_bytes(&bytes)))
+        }
+        Value::Primitive(Primitive::String(text)) => {
+            Ok(Value::string(algorithm.hash_bytes(text.as_bytes())))
+        }
+        other => Err(ShellError::new(format!(
+            "hash expects a string, binary or ls rows, found {}",
+            other.type_name()
+        ))),
+    }
+}
@@ -0,0 +1,43 @@
+use crate::{
+    context::Context,
+    error::ShellError,
+    types::{datadict::DataDict, primary::Value},
+};
+
+use super::{map_records, Args, Command};
+
+#[derive(Debug)]
+pub struct Insert;
+
+impl Command for Insert {
+    fn run(&self, args: Args) -> Result<Value, ShellError> {
+        let (column, new_value) = match args.args.as_slice() {
+            [column, new_value] => (column.to_string(), new_value),
+            _ => {
+                return Err(ShellError::new(
+                    "insert expects a column and a value, e.g. insert kb f -> f.size / 1KB",
+                ))
+            }
+        };
+
+        map_records("insert", args.instream, |mut row| {
+            if row.get(&column).is_some() {
+                return Err(ShellError::new(format!(
+                    "column {} already exists, use update instead",
+                    column
+                )));
+            }
+            let value = cell_value(new_value, &row, args.ctx)?;
+            row.insert(&column, value);
+            Ok(row)
+        })
+    }
+}
+
+/// Lambdas are called with the row, anything else is used as is
+pub fn cell_value(new_value: &Value, row: &DataDict, ctx: &Context) -> Result<Value, ShellError> {
+    match new_value {
+        Value::Lambda(lambda) => lambda.call(ctx, Value::Object(row.clone())),
+        value => Ok(value.clone()),
+    }
+}
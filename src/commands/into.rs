@@ -0,0 +1,291 @@
+use chrono::{DateTime, FixedOffset, Local, NaiveDate, NaiveDateTime, TimeZone, Utc};
+
+use crate::{
+    error::ShellError,
+    parselex::lex::{parse_unit_literal, Token},
+    stream::RushStream,
+    types::{
+        datadict::DataDict,
+        primary::{Primitive, Value},
+    },
+};
+
+use super::{Args, Command};
+
+type Converter = fn(&Primitive) -> Option<Primitive>;
+
+#[derive(Debug)]
+pub struct IntoType;
+
+impl Command for IntoType {
+    fn run(&self, args: Args) -> Result<Value, ShellError> {
+        let mut params = args.args.iter();
+
+        let target = match params.next() {
+            Some(target) => target.to_string(),
+            None => return Err(ShellError::new("into expects a type, e.g. into int")),
+        };
+
+        let convert: Converter = match target.as_str() {
+            "int" => to_int,
+            "float" => to_float,
+            "string" => to_string,
+            "bool" => to_bool,
+            "filesize" => to_filesize,
+            "duration" => to_duration,
+            "datetime" => to_datetime,
+            "path" => to_path,
+            "binary" => to_binary,
+            _ => return Err(ShellError::new(format!("into : unknown type {}", target))),
+        };
+
+        let columns: Vec<String> = params.map(|column| column.to_string()).collect();
+
+        let input = match args.instream {
+            RushStream::Internal(value) => value,
+            RushStream::External(_) => {
+                return Err(ShellError::new("external streams not supported yet"))
+            }
+            RushStream::None => return Err(ShellError::new("into expects an input")),
+        };
+
+        let mut conversion = Conversion {
+            target: &target,
+            convert,
+            columns: &columns,
+            tables: false,
+            found: vec![false; columns.len()],
+            failures: vec![],
+        };
+
+        let converted = match input {
+            Value::List(items) => items
+                .into_iter()
+                .enumerate()
+                .map(|(row, item)| conversion.apply(item, Some(row)))
+                .collect::<Result<Vec<_>, _>>()
+                .map(Value::list)?,
+            value => conversion.apply(value, None)?,
+        };
+
+        // a column that no row has is most likely a typo
+        let missing = conversion.found.iter().position(|found| !found);
+        if let (true, Some(index)) = (conversion.tables, missing) {
+            return Err(ShellError::new(format!(
+                "no column named {}",
+                columns[index]
+            )));
+        }
+
+        // every cell is converted, so one error reports all the bad ones
+        match conversion.failures.as_slice() {
+            [] => Ok(converted),
+            [failure] => Err(ShellError::new(format!(
+                "rush : into {} : {}",
+                target, failure
+            ))),
+            failures => Err(ShellError::new(format!(
+                "rush : into {} : {} cells could not be converted\n{}",
+                target,
+                failures.len(),
+                failures.join("\n")
+            ))),
+        }
+    }
+}
+
+struct Conversion<'a> {
+    target: &'a str,
+    convert: Converter,
+    columns: &'a [String],
+    tables: bool,
+    found: Vec<bool>,
+    failures: Vec<String>,
+}
+
+impl Conversion<'_> {
+    fn apply(&mut self, value: Value, row: Option<usize>) -> Result<Value, ShellError> {
+        match value {
+            Value::Object(dict) if !self.columns.is_empty() => Ok(self.apply_columns(dict, row)),
+            Value::Object(_) => Err(ShellError::new(format!(
+                "into {} expects column names for a table, e.g. into {} size",
+                self.target, self.target
+            ))),
+            value => Ok(self.convert_cell(value, row, None)),
+        }
+    }
+
+    // rows without the column are kept as they are
+    fn apply_columns(&mut self, mut dict: DataDict, row: Option<usize>) -> Value {
+        self.tables = true;
+        let columns = self.columns;
+        for (index, column) in columns.iter().enumerate() {
+            let cell = match dict.get(column) {
+                Some(cell) => cell.clone(),
+                None => continue,
+            };
+            self.found[index] = true;
+            let converted = self.convert_cell(cell, row, Some(column));
+            dict.insert(column, converted);
+        }
+        Value::Object(dict)
+    }
+
+    // a cell that can't be converted is kept as it was and its failure recorded
+    fn convert_cell(&mut self, value: Value, row: Option<usize>, column: Option<&str>) -> Value {
+        let converted = match &value {
+            Value::Primitive(primitive) => (self.convert)(primitive),
+            _ => None,
+        };
+
+        match converted {
+            Some(converted) => Value::Primitive(converted),
+            None => {
+                let location = match (row, column) {
+                    (Some(row), Some(column)) => format!(" (row {}, column {})", row, column),
+                    (Some(row), None) => format!(" (row {})", row),
+                    (None, Some(column)) => format!(" (column {})", column),
+                    (None, None) => "".to_string(),
+                };
+                self.failures.push(format!(
+                    "cannot convert {} {:?}{}",
+                    value.type_name(),
+                    value.to_string(),
+                    location
+                ));
+                value
+            }
+        }
+    }
+}
+
+fn to_int(primitive: &Primitive) -> Option<Primitive> {
+    let int = match primitive {
+        Primitive::Integer(i) => *i,
+        Primitive::Float(n) if n.is_finite() => n.trunc() as i64,
+        Primitive::Size(bytes) => i64::try_from(*bytes).ok()?,
+        Primitive::Duration(nanos) => *nanos,
+        Primitive::Bool(b) => *b as i64,
+        // nanoseconds since the unix epoch
+        Primitive::Time(_) | Primitive::Date(_) => {
+            let date = primitive.as_date()?;
+            let nanos = date.timestamp().checked_mul(1_000_000_000)?;
+            nanos.checked_add(date.timestamp_subsec_nanos() as i64)?
+        }
+        _ => primitive.as_str()?.trim().parse().ok()?,
+    };
+    Some(Primitive::Integer(int))
+}
+
+fn to_float(primitive: &Primitive) -> Option<Primitive> {
+    let float = match primitive {
+        Primitive::Integer(_) | Primitive::Float(_) | Primitive::Size(_) => primitive.as_float(),
+        Primitive::Bool(b) => *b as i64 as f64,
+        _ => primitive.as_str()?.trim().parse().ok()?,
+    };
+    Some(Primitive::Float(float))
+}
+
+fn to_string(primitive: &Primitive) -> Option<Primitive> {
+    let string = match primitive {
+        Primitive::Float(n) => n.to_string(),
+        Primitive::Time(_) => primitive.as_date()?.to_rfc3339(),
+        Primitive::Binary(bytes) => String::from_utf8(bytes.clone()).ok()?,
+        Primitive::String(_) | Primitive::Path(_) => primitive.as_str()?.into_owned(),
+        _ => primitive.format(),
+    };
+    Some(Primitive::String(string))
+}
+
+fn to_bool(primitive: &Primitive) -> Option<Primitive> {
+    let b = match primitive {
+        Primitive::Bool(b) => *b,
+        Primitive::Integer(i) => *i != 0,
+        Primitive::Float(n) => *n != 0.0,
+        _ => match primitive.as_str()?.trim().to_lowercase().as_str() {
+            "true" | "1" => true,
+            "false" | "0" => false,
+            _ => return None,
+        },
+    };
+    Some(Primitive::Bool(b))
+}
+
+pub fn to_filesize(primitive: &Primitive) -> Option<Primitive> {
+    let bytes = match primitive {
+        Primitive::Size(bytes) => *bytes,
+        Primitive::Integer(i) => u64::try_from(*i).ok()?,
+        Primitive::Float(n) if *n >= 0.0 => n.round() as u64,
+        _ => {
+            let text = primitive.as_str()?;
+            match parse_unit_literal(&text) {
+                Some(Token::Size(bytes)) => bytes,
+                Some(_) => return None,
+                None => text.trim().parse().ok()?,
+            }
+        }
+    };
+    Some(Primitive::Size(bytes))
+}
+
+pub fn to_duration(primitive: &Primitive) -> Option<Primitive> {
+    let nanos = match primitive {
+        Primitive::Duration(nanos) => *nanos,
+        Primitive::Integer(i) => *i,
+        _ => match parse_unit_literal(&primitive.as_str()?) {
+            Some(Token::Duration(nanos)) => nanos,
+            _ => return None,
+        },
+    };
+    Some(Primitive::Duration(nanos))
+}
+
+fn to_datetime(primitive: &Primitive) -> Option<Primitive> {
+    let date = match primitive {
+        Primitive::Time(_) | Primitive::Date(_) => primitive.as_date()?,
+        Primitive::Integer(nanos) => Utc.timestamp_nanos(*nanos).with_timezone(&Local).into(),
+        _ => parse_datetime(primitive.as_str()?.trim())?,
+    };
+    Some(Primitive::Date(date))
+}
+
+// Dates without an offset are taken as local time
+fn parse_datetime(text: &str) -> Option<DateTime<FixedOffset>> {
+    if let Ok(date) = DateTime::parse_from_rfc3339(text) {
+        return Some(date);
+    }
+    if let Ok(date) = DateTime::parse_from_rfc2822(text) {
+        return Some(date);
+    }
+    if let Ok(date) = DateTime::parse_from_str(text, "%Y-%m-%d %H:%M:%S %z") {
+        return Some(date);
+    }
+
+    let naive = NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M:%S")
+        .or_else(|_| NaiveDateTime::parse_from_str(text, "%Y-%m-%dT%H:%M:%S"))
+        .ok()
+        .or_else(|| {
+            NaiveDate::parse_from_str(text, "%Y-%m-%d")
+                .ok()?
+                .and_hms_opt(0, 0, 0)
+        })?;
+    let local = Local.from_local_datetime(&naive).single()?;
+    Some(local.into())
+}
+
+fn to_path(primitive: &Primitive) -> Option<Primitive> {
+    match primitive {
+        Primitive::String(_) | Primitive::Path(_) => {
+            Some(Primitive::Path(primitive.as_str()?.into_owned().into()))
+        }
+        _ => None,
+    }
+}
+
+fn to_binary(primitive: &Primitive) -> Option<Primitive> {
+    match primitive {
+        Primitive::Binary(bytes) => Some(Primitive::Binary(bytes.clone())),
+        Primitive::String(s) => Some(Primitive::Binary(s.as_bytes().to_vec())),
+        _ => None,
+    }
+}
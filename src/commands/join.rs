@@ -0,0 +1,130 @@
+use std::collections::HashSet;
+
+use indexmap::IndexMap;
+
+use crate::{
+    error::ShellError,
+    types::{datadict::DataDict, primary::Value},
+};
+
+use super::{list_input, Args, Command};
+
+#[derive(Debug)]
+pub struct Join;
+
+#[derive(PartialEq, Eq)]
+enum JoinKind {
+    Inner,
+    Left,
+    Outer,
+}
+
+impl Command for Join {
+    fn run(&self, args: Args) -> Result<Value, ShellError> {
+        let mut right = None;
+        let mut column = None;
+        let mut kind = JoinKind::Inner;
+
+        // join (open owners.csv) --on pid --left
+        let mut params = args.args.iter();
+        while let Some(param) = params.next() {
+            match param {
+                Value::List(table) => right = Some(table),
+                _ => match param.to_string().as_str() {
+                    "--on" => column = params.next().map(|column| column.to_string()),
+                    "--inner" => kind = JoinKind::Inner,
+                    "--left" => kind = JoinKind::Left,
+                    "--outer" => kind = JoinKind::Outer,
+                    other => {
+                        return Err(ShellError::new(format!(
+                            "join : unexpected argument {}",
+                            other
+                        )))
+                    }
+                },
+            }
+        }
+
+        let (right, column) = match (right, column) {
+            (Some(right), Some(column)) => (right, column),
+            _ => {
+                return Err(ShellError::new(
+                    "join expects a table and a column, e.g. join (open owners.csv) --on pid",
+                ))
+            }
+        };
+
+        let left = list_input("join", args.instream)?;
+
+        // right rows by key, keys are compared as text so 42 matches "42"
+        let mut right_rows: IndexMap<String, Vec<&DataDict>> = IndexMap::new();
+        for row in right {
+            let dict = as_record(row)?;
+            let key = key_of(dict, &column)?;
+            right_rows.entry(key).or_default().push(dict);
+        }
+
+        let mut matched_keys = HashSet::new();
+        let mut joined = vec![];
+
+        for row in &left {
+            let dict = as_record(row)?;
+            let key = key_of(dict, &column)?;
+            match right_rows.get(&key) {
+                Some(matches) => {
+                    for other in matches {
+                        joined.push(Value::object(combine(dict, other, &column)));
+                    }
+                    matched_keys.insert(key);
+                }
+                None if kind != JoinKind::Inner => joined.push(row.clone()),
+                None => {}
+            }
+        }
+
+        if kind == JoinKind::Outer {
+            for (key, rows) in &right_rows {
+                if !matched_keys.contains(key) {
+                    joined.extend(rows.iter().map(|row| Value::object((*row).clone())));
+                }
+            }
+        }
+
+        Ok(Value::list(joined))
+    }
+}
+
+fn as_record(row: &Value) -> Result<&DataDict, ShellError> {
+    match row {
+        Value::Object(dict) => Ok(dict),
+        other => Err(ShellError::new(format!(
+            "join expects tables, found a list of {}",
+            other.type_name()
+        ))),
+    }
+}
+
+fn key_of(dict: &DataDict, column: &str) -> Result<String, ShellError> {
+    match dict.get(column) {
+        Some(key) => Ok(key.to_string()),
+        None => Err(ShellError::new(format!(
+            "join : no column named {}",
+            column
+        ))),
+    }
+}
+
+// Right columns clashing with left ones get a _right suffix
+fn combine(left: &DataDict, right: &DataDict, column: &str) -> DataDict {
+    let mut row = left.clone();
+    for (name, value) in right.iter() {
+        if name == column {
+            continue;
+        }
+        match row.get(name) {
+            Some(_) => row.insert(format!("{}_right", name), value.clone()),
+            None => row.insert(name, value.clone()),
+        }
+    }
+    row
+}
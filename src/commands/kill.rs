@@ -0,0 +1,147 @@
+use std::io;
+
+use crate::{
+    error::ShellError,
+    stream::RushStream,
+    types::{
+        datadict::DataDict,
+        primary::{Primitive, Value},
+    },
+};
+
+use super::{Args, Command};
+
+#[derive(Debug)]
+pub struct Kill;
+
+const SIGNALS: [(&str, i32); 10] = [
+    ("HUP", libc::SIGHUP),
+    ("INT", libc::SIGINT),
+    ("QUIT", libc::SIGQUIT),
+    ("KILL", libc::SIGKILL),
+    ("USR1", libc::SIGUSR1),
+    ("USR2", libc::SIGUSR2),
+    ("TERM", libc::SIGTERM),
+    ("CONT", libc::SIGCONT),
+    ("STOP", libc::SIGSTOP),
+    ("TSTP", libc::SIGTSTP),
+];
+
+impl Command for Kill {
+    fn run(&self, args: Args) -> Result<Value, ShellError> {
+        let mut signal = libc::SIGTERM;
+        let mut pids = vec![];
+
+        // kill 1234 5678 --signal KILL
+        let mut params = args.args.iter().peekable();
+
+        // kill -9 1234 and kill -KILL 1234, the leading form of kill(1)
+        let leading = params.peek().and_then(|first| match first {
+            Value::Primitive(Primitive::Integer(n)) if *n < 0 => Some(n.unsigned_abs().to_string()),
+            Value::Primitive(Primitive::String(arg)) => arg
+                .strip_prefix('-')
+                .filter(|name| !name.is_empty() && !name.starts_with('-') && *name != "s")
+                .map(str::to_string),
+            _ => None,
+        });
+        if let Some(name) = leading {
+            signal = parse_signal(&name)?;
+            params.next();
+        }
+
+        while let Some(arg) = params.next() {
+            match arg.to_string().as_str() {
+                "--signal" | "-s" => match params.next() {
+                    Some(name) => signal = parse_signal(&name.to_string())?,
+                    None => return Err(ShellError::new("kill : --signal expects a signal")),
+                },
+                _ => pids.push(arg.to_int()?),
+            }
+        }
+
+        // ps | filter p -> p.name == "node" | kill, which may match nothing
+        let piped = matches!(args.instream, RushStream::Internal(Value::List(_)));
+        match args.instream {
+            RushStream::Internal(Value::List(rows)) => {
+                for row in rows {
+                    pids.push(pid_of(&row)?);
+                }
+            }
+            RushStream::Internal(row) => pids.push(pid_of(&row)?),
+            RushStream::External(_) => {
+                return Err(ShellError::new("external streams not supported yet"))
+            }
+            RushStream::None => {}
+        }
+
+        if pids.is_empty() && !piped {
+            return Err(ShellError::new("kill expects a pid, e.g. kill 1234"));
+        }
+
+        let results = pids
+            .into_iter()
+            .map(|pid| Value::object(send_signal(pid, signal)))
+            .collect::<Vec<_>>();
+
+        Ok(Value::list(results))
+    }
+}
+
+// TERM, SIGTERM and 15 all name the same signal
+fn parse_signal(name: &str) -> Result<i32, ShellError> {
+    if let Ok(number) = name.parse::<i32>() {
+        return Ok(number);
+    }
+
+    let upper = name.to_uppercase();
+    let name = upper.strip_prefix("SIG").unwrap_or(&upper);
+    SIGNALS
+        .iter()
+        .find(|(signal_name, _)| *signal_name == name)
+        .map(|(_, signal)| *signal)
+        .ok_or_else(|| ShellError::new(format!("kill : unknown signal {}", name)))
+}
+
+fn signal_name(signal: i32) -> String {
+    match SIGNALS.iter().find(|(_, number)| *number == signal) {
+        Some((name, _)) => name.to_string(),
+        None => signal.to_string(),
+    }
+}
+
+// rows from ps are killed by their pid column
+fn pid_of(row: &Value) -> Result<i64, ShellError> {
+    match row {
+        Value::Object(_) => row
+            .get_field("pid")
+            .map_err(|_| ShellError::new("kill : input rows need a pid column"))?
+            .to_int(),
+        _ => row.to_int(),
+    }
+}
+
+fn send_signal(pid: i64, signal: i32) -> DataDict {
+    let result = match libc::pid_t::try_from(pid) {
+        // pids below one would signal whole process groups
+        Ok(target) if target > 0 => match unsafe { libc::kill(target, signal) } {
+            0 => Ok(()),
+            _ => Err(io::Error::last_os_error().to_string()),
+        },
+        _ => Err("invalid pid".to_string()),
+    };
+
+    let mut dict = DataDict::default();
+    dict.insert("pid", Value::int(pid));
+    dict.insert("signal", Value::string(signal_name(signal)));
+    match result {
+        Ok(()) => {
+            dict.insert("success", Value::bool(true));
+            dict.insert("error", Value::string(""));
+        }
+        Err(err) => {
+            dict.insert("success", Value::bool(false));
+            dict.insert("error", Value::string(err));
+        }
+    }
+    dict
+}
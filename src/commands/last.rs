@@ -0,0 +1,51 @@
+use crate::{error::ShellError, types::primary::Value};
+
+use super::{count_arg, list_input, Args, Command};
+
+#[derive(Debug)]
+pub struct Last;
+
+impl Command for Last {
+    fn run(&self, args: Args) -> Result<Value, ShellError> {
+        let mut objects = list_input("last", args.instream)?;
+
+        // without a count the item itself is returned
+        if args.args.is_empty() {
+            return objects
+                .pop()
+                .ok_or_else(|| ShellError::new("last : the list is empty"));
+        }
+
+        let count = count_arg("last", args.args.first())?;
+        objects.drain(..objects.len().saturating_sub(count));
+        Ok(Value::list(objects))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::eval_with;
+
+    fn last(line: &str) -> Value {
+        eval_with("last", Last, line).unwrap()
+    }
+
+    fn fails(line: &str) -> bool {
+        eval_with("last", Last, line).is_err()
+    }
+
+    #[test]
+    fn without_a_count_the_item() {
+        assert_eq!(last("[1 2 3] | last"), Value::int(3));
+        assert_eq!(last("[{a: 1} {a: 2}] | last"), last("{a: 2}"));
+        assert!(fails("[] | last"));
+    }
+
+    #[test]
+    fn with_a_count_a_list() {
+        assert_eq!(last("[1 2 3] | last 2"), last("[2 3]"));
+        assert_eq!(last("[1 2] | last 5"), last("[1 2]"));
+        assert_eq!(last("[1 2] | last 0"), last("[]"));
+    }
+}
@@ -1,7 +1,13 @@
-use std::{env, fs, path::PathBuf};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use users::UsersCache;
 
 use crate::{
     error::ShellError,
+    paths::{expand_path, resolve_path},
     types::{direntry::direntry_dict, primary::Value},
 };
 
@@ -10,34 +16,137 @@ use super::{Args, Command};
 #[derive(Debug)]
 pub struct Ls;
 
+#[derive(Default)]
+struct LsOptions {
+    all: bool,
+    long: bool,
+    recursive: bool,
+    directory: bool,
+}
+
 impl Command for Ls {
     fn run(&self, args: Args) -> Result<Value, ShellError> {
-        let cwd = env::current_dir()?;
+        let mut options = LsOptions::default();
+        let mut patterns = vec![];
 
-        let target_dir = if !args.args.is_empty() {
-            let path_arg = &args.args[0].to_string();
-            let input_path = PathBuf::from(path_arg.to_string());
-
-            if input_path.is_absolute() || input_path.starts_with("~") {
-                let expanded = format!("{}", shellexpand::tilde(path_arg));
-                PathBuf::from(expanded)
-            } else {
-                cwd.join(input_path)
+        // flags can be combined, e.g. ls -la
+        for arg in &args.args {
+            let arg = arg.to_string();
+            match arg.as_str() {
+                "--all" => options.all = true,
+                "--long" => options.long = true,
+                "--recursive" => options.recursive = true,
+                "--directory" => options.directory = true,
+                flags if flags.starts_with('-') && flags.len() > 1 => {
+                    for flag in flags.chars().skip(1) {
+                        match flag {
+                            'a' => options.all = true,
+                            'l' => options.long = true,
+                            'r' => options.recursive = true,
+                            'd' => options.directory = true,
+                            _ => {
+                                return Err(ShellError::new(format!("ls : unknown flag -{}", flag)))
+                            }
+                        }
+                    }
+                }
+                _ => patterns.push(arg),
             }
-        } else {
-            cwd
+        }
+
+        let owners = match options.long {
+            true => Some(UsersCache::new()),
+            false => None,
+        };
+        let lister = Lister {
+            options,
+            owners: owners.as_ref(),
         };
 
-        let paths = fs::read_dir(target_dir)?;
+        let mut entries = vec![];
 
-        let mut dir_entries = vec![];
+        // without a path the working directory is listed with bare names
+        if patterns.is_empty() {
+            match lister.options.directory {
+                true => lister.push_entry(Path::new("."), PathBuf::from("."), &mut entries)?,
+                false => lister.list_dir(Path::new("."), Path::new(""), &mut entries)?,
+            }
+            return Ok(Value::list(entries));
+        }
 
-        for path in paths {
-            let dict = direntry_dict(path?);
-            let entry = Value::object(dict?);
-            dir_entries.push(entry);
+        let cwd = args.env.cwd();
+        for pattern in &patterns {
+            let expanded = expand_path(pattern).to_string_lossy().into_owned();
+
+            // globs list what they match, like a shell would
+            if expanded.contains(['*', '?', '[']) {
+                let matches = glob::glob(&expanded)
+                    .map_err(|err| ShellError::new(format!("ls : {} : {}", pattern, err)))?;
+                for path in matches.flatten() {
+                    lister.push_entry(&path, path.clone(), &mut entries)?;
+                }
+                continue;
+            }
+
+            let path = resolve_path(&cwd, pattern);
+            let metadata = fs::metadata(&path)
+                .map_err(|err| ShellError::new(format!("ls : {} : {}", pattern, err)))?;
+
+            match metadata.is_dir() && !lister.options.directory {
+                true => lister.list_dir(&path, Path::new(pattern), &mut entries)?,
+                false => lister.push_entry(&path, PathBuf::from(pattern), &mut entries)?,
+            }
+        }
+
+        Ok(Value::list(entries))
+    }
+}
+
+struct Lister<'a> {
+    options: LsOptions,
+    owners: Option<&'a UsersCache>,
+}
+
+impl Lister<'_> {
+    fn push_entry(
+        &self,
+        path: &Path,
+        name: PathBuf,
+        entries: &mut Vec<Value>,
+    ) -> Result<(), ShellError> {
+        let dict = direntry_dict(path, name, self.owners)?;
+        entries.push(Value::object(dict));
+        Ok(())
+    }
+
+    // names are shown relative to what was asked for, e.g. src/commands/ls.rs
+    fn list_dir(
+        &self,
+        dir: &Path,
+        display: &Path,
+        entries: &mut Vec<Value>,
+    ) -> Result<(), ShellError> {
+        let mut children = fs::read_dir(dir)?.collect::<Result<Vec<_>, _>>()?;
+        children.sort_by_key(|child| child.file_name());
+
+        for child in children {
+            let file_name = child.file_name();
+            if !self.options.all && file_name.to_string_lossy().starts_with('.') {
+                continue;
+            }
+
+            let path = child.path();
+            let name = display.join(&file_name);
+            self.push_entry(&path, name.clone(), entries)?;
+
+            // symlinked directories are not followed
+            let is_dir = child.file_type().is_ok_and(|kind| kind.is_dir());
+            if self.options.recursive && is_dir {
+                // unreadable subdirectories are skipped rather than ending the listing
+                let _ = self.list_dir(&path, &name, entries);
+            }
         }
 
-        Ok(Value::list(dir_entries))
+        Ok(())
     }
 }
@@ -0,0 +1,105 @@
+use crate::{
+    error::ShellError, eval::binary_op, parselex::lex::Operator, stream::RushStream,
+    types::primary::Value,
+};
+
+use super::{Args, Command};
+
+#[derive(Debug)]
+pub struct Math;
+
+impl Command for Math {
+    fn run(&self, args: Args) -> Result<Value, ShellError> {
+        let operation = match args.args.first() {
+            Some(operation) => operation.to_string(),
+            None => return Err(ShellError::new("math expects an operation, e.g. math sum")),
+        };
+
+        let values = match args.instream {
+            RushStream::Internal(Value::List(list)) => list,
+            RushStream::External(_) => {
+                return Err(ShellError::new("external streams not supported yet"))
+            }
+            _ => return Err(ShellError::new("math expects a list of numbers")),
+        };
+
+        if values.is_empty() && operation != "sum" {
+            return Err(ShellError::new(format!(
+                "math {} : the list is empty",
+                operation
+            )));
+        }
+
+        match operation.as_str() {
+            "sum" => sum(values),
+            "avg" => average(values),
+            "min" => sorted(values).map(|mut values| values.remove(0)),
+            "max" => sorted(values).map(|mut values| values.pop().unwrap()),
+            "median" => median(values),
+            _ => Err(ShellError::new(format!(
+                "math : unknown operation {}",
+                operation
+            ))),
+        }
+    }
+}
+
+// Sums use the same arithmetic as expressions, so sizes and durations keep their unit
+fn sum(values: Vec<Value>) -> Result<Value, ShellError> {
+    let mut values = values.into_iter();
+    let first = match values.next() {
+        Some(first) => first,
+        None => return Ok(Value::int(0)),
+    };
+    values.try_fold(first, |total, value| binary_op(Operator::Add, total, value))
+}
+
+fn average(values: Vec<Value>) -> Result<Value, ShellError> {
+    let count = Value::int(values.len() as i64);
+    binary_op(Operator::Div, sum(values)?, count)
+}
+
+fn median(values: Vec<Value>) -> Result<Value, ShellError> {
+    let mut values = sorted(values)?;
+    let middle = values.len() / 2;
+
+    match values.len() % 2 {
+        1 => Ok(values.swap_remove(middle)),
+        _ => {
+            let upper = values.swap_remove(middle);
+            let lower = values.swap_remove(middle - 1);
+            average(vec![lower, upper])
+        }
+    }
+}
+
+// Values must all be comparable with each other, e.g. numbers or sizes but not both
+fn sorted(mut values: Vec<Value>) -> Result<Vec<Value>, ShellError> {
+    let mut primitives = vec![];
+    for value in &values {
+        match value {
+            Value::Primitive(primitive) => primitives.push(primitive),
+            _ => {
+                return Err(ShellError::new(format!(
+                    "math expects a list of numbers, found {}",
+                    value.type_name()
+                )))
+            }
+        }
+    }
+
+    if let Some(first) = primitives.first() {
+        for primitive in &primitives {
+            if first.compare(primitive).is_none() {
+                return Err(ShellError::new(format!(
+                    "cannot compare {} and {}",
+                    first.type_name(),
+                    primitive.type_name()
+                )));
+            }
+        }
+    }
+
+    values.sort();
+    Ok(values)
+}
@@ -0,0 +1,83 @@
+use crate::{
+    error::ShellError,
+    stream::RushStream,
+    types::{datadict::DataDict, primary::Value},
+};
+
+use super::{Args, Command};
+
+#[derive(Debug)]
+pub struct Merge;
+
+impl Command for Merge {
+    fn run(&self, args: Args) -> Result<Value, ShellError> {
+        let other = match args.args.first() {
+            Some(other) => other,
+            None => return Err(ShellError::new("merge expects a record or a table")),
+        };
+
+        let input = match args.instream {
+            RushStream::Internal(value) => value,
+            RushStream::External(_) => {
+                return Err(ShellError::new("external streams not supported yet"))
+            }
+            RushStream::None => return Err(ShellError::new("merge expects an input")),
+        };
+
+        // tables merge row by row, a record is merged into every row
+        match (input, other) {
+            (Value::Object(dict), Value::Object(other)) => Ok(Value::object(merge(dict, other))),
+            (Value::List(rows), Value::Object(other)) => rows
+                .into_iter()
+                .map(|row| merge_row(row, Some(other)))
+                .collect::<Result<Vec<_>, _>>()
+                .map(Value::list),
+            (Value::List(rows), Value::List(others)) => {
+                let len = rows.len().max(others.len());
+                let mut rows = rows.into_iter();
+                let mut merged = vec![];
+                for i in 0..len {
+                    let other = match others.get(i) {
+                        Some(Value::Object(other)) => Some(other),
+                        Some(other) => {
+                            return Err(ShellError::new(format!(
+                                "cannot merge a {} into a table",
+                                other.type_name()
+                            )))
+                        }
+                        None => None,
+                    };
+                    let row = rows
+                        .next()
+                        .unwrap_or_else(|| Value::object(DataDict::default()));
+                    merged.push(merge_row(row, other)?);
+                }
+                Ok(Value::list(merged))
+            }
+            (input, other) => Err(ShellError::new(format!(
+                "cannot merge a {} into a {}",
+                other.type_name(),
+                input.type_name()
+            ))),
+        }
+    }
+}
+
+fn merge_row(row: Value, other: Option<&DataDict>) -> Result<Value, ShellError> {
+    match (row, other) {
+        (Value::Object(dict), Some(other)) => Ok(Value::object(merge(dict, other))),
+        (row @ Value::Object(_), None) => Ok(row),
+        (row, _) => Err(ShellError::new(format!(
+            "merge expects a table, found a list of {}",
+            row.type_name()
+        ))),
+    }
+}
+
+// Columns of the second record win
+fn merge(mut dict: DataDict, other: &DataDict) -> DataDict {
+    for (name, value) in other.iter() {
+        dict.insert(name, value.clone());
+    }
+    dict
+}
@@ -0,0 +1,44 @@
+use std::fs;
+
+use crate::{error::ShellError, paths::expand_path, types::primary::Value};
+
+use super::{
+    fileops::{change_row, piped_paths, FileArgs},
+    Args, Command,
+};
+
+#[derive(Debug)]
+pub struct Mkdir;
+
+impl Command for Mkdir {
+    fn run(&self, args: Args) -> Result<Value, ShellError> {
+        let file_args =
+            FileArgs::parse("mkdir", &args.args, &[("parents", 'p'), ("dry-run", 'n')])?;
+        let parents = file_args.has("parents");
+        let dry_run = file_args.has("dry-run");
+
+        // new directories are not globbed, they don't exist yet
+        let mut paths = piped_paths("mkdir", args.instream)?;
+        paths.extend(file_args.paths.iter().map(|path| expand_path(path)));
+        if paths.is_empty() {
+            return Err(ShellError::new("mkdir expects a directory name"));
+        }
+
+        let mut changes = vec![];
+        for path in paths {
+            // mkdir -p is fine with directories that are already there
+            if parents && path.is_dir() {
+                continue;
+            }
+
+            let result = match (dry_run, parents) {
+                (true, _) => Ok(()),
+                (false, true) => fs::create_dir_all(&path).map_err(ShellError::from),
+                (false, false) => fs::create_dir(&path).map_err(ShellError::from),
+            };
+            changes.push(change_row("created", &path, None, result, dry_run));
+        }
+
+        Ok(Value::list(changes))
+    }
+}
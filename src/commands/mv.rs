@@ -0,0 +1,31 @@
+use crate::{error::ShellError, types::primary::Value};
+
+use super::{
+    cp::sources_and_destination,
+    fileops::{change_row, destination_for, move_path, FileArgs},
+    Args, Command,
+};
+
+#[derive(Debug)]
+pub struct Mv;
+
+impl Command for Mv {
+    fn run(&self, args: Args) -> Result<Value, ShellError> {
+        let mut file_args = FileArgs::parse("mv", &args.args, &[("dry-run", 'n')])?;
+        let dry_run = file_args.has("dry-run");
+
+        let (sources, destination) = sources_and_destination("mv", &mut file_args, args.instream)?;
+
+        let mut changes = vec![];
+        for source in sources {
+            let target = destination_for(&source, &destination);
+            let result = match dry_run {
+                true => Ok(()),
+                false => move_path(&source, &target),
+            };
+            changes.push(change_row("moved", &source, Some(&target), result, dry_run));
+        }
+
+        Ok(Value::list(changes))
+    }
+}
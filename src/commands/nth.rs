@@ -0,0 +1,62 @@
+use crate::{error::ShellError, types::primary::Value};
+
+use super::{list_input, Args, Command};
+
+#[derive(Debug)]
+pub struct Nth;
+
+impl Command for Nth {
+    fn run(&self, args: Args) -> Result<Value, ShellError> {
+        let objects = list_input("nth", args.instream)?;
+
+        let mut selected = vec![];
+        for arg in &args.args {
+            let index = arg.to_int()?;
+            let item = usize::try_from(index)
+                .ok()
+                .and_then(|index| objects.get(index))
+                .ok_or_else(|| ShellError::new(format!("nth : index {} out of range", index)))?;
+            selected.push(item.clone());
+        }
+
+        // nth 2 gives the item, nth 0 3 5 a list
+        match selected.len() {
+            0 => Err(ShellError::new("nth expects indices, e.g. nth 0 3 5")),
+            1 => Ok(selected.swap_remove(0)),
+            _ => Ok(Value::list(selected)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::eval_with;
+
+    fn nth(line: &str) -> Value {
+        eval_with("nth", Nth, line).unwrap()
+    }
+
+    fn fails(line: &str) -> bool {
+        eval_with("nth", Nth, line).is_err()
+    }
+
+    #[test]
+    fn one_index_is_the_item() {
+        assert_eq!(nth("[a b c] | nth 1"), Value::string("b"));
+        assert_eq!(nth("[{a: 1} {a: 2}] | nth 1"), nth("{a: 2}"));
+    }
+
+    #[test]
+    fn indices_keep_their_order_and_repeats() {
+        assert_eq!(nth("[a b c] | nth 2 0"), nth("[c a]"));
+        assert_eq!(nth("[a b c] | nth 1 1"), nth("[b b]"));
+    }
+
+    #[test]
+    fn out_of_range() {
+        assert!(fails("[a b c] | nth 3"));
+        assert!(fails("[a b c] | nth -1"));
+        assert!(fails("[a b c] | nth"));
+    }
+}
@@ -0,0 +1,103 @@
+use std::fs;
+
+use crate::{error::ShellError, formats::Format, paths::expand_path, types::primary::Value};
+
+use super::{Args, Command};
+
+// files with a nul byte this early on are taken as binary
+const BINARY_PROBE: usize = 8192;
+
+#[derive(Debug)]
+pub struct Open;
+
+impl Command for Open {
+    fn run(&self, args: Args) -> Result<Value, ShellError> {
+        let mut raw = false;
+        let mut lines = false;
+        let mut path_arg = None;
+        for arg in &args.args {
+            match arg.to_string().as_str() {
+                "--raw" | "-r" => raw = true,
+                "--lines" | "-l" => lines = true,
+                path => path_arg = Some(path.to_string()),
+            }
+        }
+
+        let path_arg = match path_arg {
+            Some(path) => path,
+            None => return Err(ShellError::new("open expects a file path")),
+        };
+        let path = expand_path(&path_arg);
+
+        // commands return whole values, so the file is read at once, binary files included
+        let bytes = fs::read(&path)
+            .map_err(|err| ShellError::new(format!("open : {} : {}", path_arg, err)))?;
+
+        if bytes.iter().take(BINARY_PROBE).any(|byte| *byte == 0) {
+            return Ok(Value::binary(bytes));
+        }
+        // the buffer is reused for the string, text is never copied
+        let contents = match String::from_utf8(bytes) {
+            Ok(contents) => contents,
+            Err(err) => return Ok(Value::binary(err.into_bytes())),
+        };
+
+        if lines {
+            return Ok(Value::list(
+                contents.lines().map(Value::string).collect::<Vec<_>>(),
+            ));
+        }
+        // --raw skips format detection, e.g. open --raw data.json | save copy.json
+        match (raw, Format::from_path(&path)) {
+            (false, Some(format)) => format.parse(&contents),
+            _ => Ok(Value::string(contents)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, path::PathBuf};
+
+    use super::*;
+    use crate::{commands::eval_with, types::primary::Primitive};
+
+    fn scratch_file(name: &str, contents: &[u8]) -> PathBuf {
+        let path = env::temp_dir().join(format!("rush-open-{}-{}", std::process::id(), name));
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
+    fn open(line: &str) -> Value {
+        eval_with("open", Open, line).unwrap()
+    }
+
+    #[test]
+    fn binary_files_are_read_whole() {
+        let bytes: Vec<u8> = (0..20_000).map(|i| (i % 256) as u8).collect();
+        let path = scratch_file("data.bin", &bytes);
+        match open(&format!("open {}", path.display())) {
+            Value::Primitive(Primitive::Binary(opened)) => assert_eq!(opened, bytes),
+            other => panic!("expected binary, found {:?}", other),
+        }
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn text_and_lines() {
+        let path = scratch_file("notes.txt", b"one\r\ntwo\nthree");
+        assert_eq!(
+            open(&format!("open {}", path.display())),
+            Value::string("one\r\ntwo\nthree")
+        );
+        assert_eq!(
+            open(&format!("open --lines {}", path.display())),
+            Value::list(vec![
+                Value::string("one"),
+                Value::string("two"),
+                Value::string("three")
+            ])
+        );
+        fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,156 @@
+use regex::Regex;
+
+use crate::{
+    error::ShellError,
+    stream::RushStream,
+    types::{datadict::DataDict, primary::Value},
+};
+
+use super::{strings::as_text, Args, Command};
+
+#[derive(Debug)]
+pub struct Parse;
+
+impl Command for Parse {
+    fn run(&self, args: Args) -> Result<Value, ShellError> {
+        let mut params: Vec<String> = args.args.iter().map(|arg| arg.to_string()).collect();
+
+        let raw_regex = match params.iter().position(|p| p == "--regex" || p == "-r") {
+            Some(index) => {
+                params.remove(index);
+                true
+            }
+            None => false,
+        };
+
+        let (pattern, column) = match params.as_slice() {
+            [pattern] => (pattern, None),
+            [pattern, column] => (pattern, Some(column)),
+            _ => {
+                return Err(ShellError::new(
+                    "parse expects a pattern, e.g. parse \"{name}-{version}.tar.gz\"",
+                ))
+            }
+        };
+
+        let regex = match raw_regex {
+            true => Regex::new(pattern),
+            false => Regex::new(&template_regex(pattern)),
+        }
+        .map_err(|err| ShellError::new(format!("parse : invalid pattern : {}", err)))?;
+
+        let input = match args.instream {
+            RushStream::Internal(value) => value,
+            RushStream::External(_) => {
+                return Err(ShellError::new("external streams not supported yet"))
+            }
+            RushStream::None => return Err(ShellError::new("parse expects an input")),
+        };
+
+        match (input, column) {
+            // captures are added to each row, rows that don't match get empty cells
+            (Value::List(rows), Some(column)) => rows
+                .into_iter()
+                .map(|row| match row {
+                    Value::Object(mut dict) => {
+                        let text = dict.get(column).and_then(as_text).ok_or_else(|| {
+                            ShellError::new(format!("parse : no text column named {}", column))
+                        })?;
+                        let captured = captures(&regex, &text).unwrap_or_else(|| {
+                            regex
+                                .capture_names()
+                                .flatten()
+                                .map(|name| (name.to_string(), Value::string("")))
+                                .collect()
+                        });
+                        for (name, value) in captured {
+                            dict.insert(name, value);
+                        }
+                        Ok(Value::object(dict))
+                    }
+                    other => Err(ShellError::new(format!(
+                        "parse expects a table, found a list of {}",
+                        other.type_name()
+                    ))),
+                })
+                .collect::<Result<Vec<_>, _>>()
+                .map(Value::list),
+            // lines that don't match are left out
+            (Value::List(items), None) => {
+                let mut parsed = vec![];
+                for item in &items {
+                    let text = as_text(item).ok_or_else(|| {
+                        ShellError::new(format!(
+                            "parse expects strings, found {}",
+                            item.type_name()
+                        ))
+                    })?;
+                    if let Some(captured) = captures(&regex, &text) {
+                        parsed.push(record(captured));
+                    }
+                }
+                Ok(Value::list(parsed))
+            }
+            (value, _) => {
+                let text = as_text(&value).ok_or_else(|| {
+                    ShellError::new(format!(
+                        "parse expects a string, found {}",
+                        value.type_name()
+                    ))
+                })?;
+                // multi-line output is parsed line by line
+                let parsed = text
+                    .lines()
+                    .filter_map(|line| captures(&regex, line))
+                    .map(record)
+                    .collect::<Vec<_>>();
+                match parsed.len() {
+                    0 => Err(ShellError::new("parse : the pattern did not match")),
+                    1 => Ok(parsed.into_iter().next().unwrap()),
+                    _ => Ok(Value::list(parsed)),
+                }
+            }
+        }
+    }
+}
+
+// "{name}-{version}.tar.gz" becomes ^(?P<name>.*?)-(?P<version>.*?)\.tar\.gz$
+fn template_regex(template: &str) -> String {
+    let mut regex = String::from("^");
+    let mut rest = template;
+
+    while let Some(open) = rest.find('{') {
+        let close = match rest[open..].find('}') {
+            Some(close) => open + close,
+            None => break,
+        };
+        regex.push_str(&regex::escape(&rest[..open]));
+        regex.push_str(&format!("(?P<{}>.*?)", &rest[open + 1..close]));
+        rest = &rest[close + 1..];
+    }
+
+    regex.push_str(&regex::escape(rest));
+    regex.push('$');
+    regex
+}
+
+fn captures(regex: &Regex, text: &str) -> Option<Vec<(String, Value)>> {
+    let captures = regex.captures(text)?;
+    let named = regex
+        .capture_names()
+        .flatten()
+        .map(|name| {
+            let value = captures.name(name).map_or("", |m| m.as_str());
+            (name.to_string(), Value::string(value))
+        })
+        .collect();
+    Some(named)
+}
+
+fn record(entries: Vec<(String, Value)>) -> Value {
+    let mut dict = DataDict::default();
+    for (name, value) in entries {
+        dict.insert(name, value);
+    }
+    Value::object(dict)
+}
@@ -0,0 +1,132 @@
+use std::{fs, path::Path};
+
+use crate::{
+    error::ShellError,
+    paths::{expand_path, relative_to, resolve_path},
+    stream::RushStream,
+    types::{datadict::DataDict, primary::Value},
+};
+
+use super::{strings::map_strings, Args, Command};
+
+#[derive(Debug)]
+pub struct PathCommand;
+
+impl Command for PathCommand {
+    fn run(&self, args: Args) -> Result<Value, ShellError> {
+        let mut params = args.args;
+
+        if params.is_empty() {
+            return Err(ShellError::new(
+                "path expects an operation, e.g. path parse",
+            ));
+        }
+        let operation = params.remove(0).to_string();
+
+        let arity = match operation.as_str() {
+            "parse" | "basename" | "dirname" | "expand" | "canonicalize" => 0,
+            "join" | "relative-to" => 1,
+            _ => {
+                return Err(ShellError::new(format!(
+                    "path : unknown operation {}",
+                    operation
+                )))
+            }
+        };
+
+        // without an input the first argument is the path, e.g. path expand ~/src
+        let input = match args.instream {
+            RushStream::Internal(value) => value,
+            RushStream::External(_) => {
+                return Err(ShellError::new("external streams not supported yet"))
+            }
+            RushStream::None if !params.is_empty() => Value::string(params.remove(0).to_string()),
+            RushStream::None => {
+                return Err(ShellError::new(format!(
+                    "path {} expects a path",
+                    operation
+                )))
+            }
+        };
+
+        if params.len() < arity {
+            return Err(ShellError::new(format!(
+                "path {} expects {} argument(s)",
+                operation, arity
+            )));
+        }
+        // whatever follows the operation's own arguments are columns
+        let columns: Vec<String> = params
+            .split_off(arity)
+            .iter()
+            .map(|c| c.to_string())
+            .collect();
+
+        let cwd = args.env.cwd();
+        let command = format!("path {}", operation);
+        match operation.as_str() {
+            "parse" => map_strings(&command, input, &columns, &|s| {
+                Ok(Value::object(parse_path(Path::new(s))))
+            }),
+            "basename" => map_strings(&command, input, &columns, &|s| {
+                Ok(Value::string(basename(Path::new(s))))
+            }),
+            "dirname" => map_strings(&command, input, &columns, &|s| {
+                Ok(Value::path(dirname(Path::new(s))))
+            }),
+            "expand" => map_strings(&command, input, &columns, &|s| {
+                Ok(Value::path(expand_path(s)))
+            }),
+            "canonicalize" => map_strings(&command, input, &columns, &|s| {
+                fs::canonicalize(resolve_path(&cwd, s))
+                    .map(Value::path)
+                    .map_err(|err| ShellError::new(format!("{} : {} : {}", command, s, err)))
+            }),
+            "join" => {
+                let part = params[0].to_string();
+                map_strings(&command, input, &columns, &|s| {
+                    Ok(Value::path(Path::new(s).join(&part)))
+                })
+            }
+            _ => {
+                let base = resolve_path(&cwd, &params[0].to_string());
+                map_strings(&command, input, &columns, &|s| {
+                    Ok(Value::path(relative_to(&resolve_path(&cwd, s), &base)))
+                })
+            }
+        }
+    }
+}
+
+// src/main.rs is {parent: src, stem: main, extension: rs}
+fn parse_path(path: &Path) -> DataDict {
+    let text = |part: Option<&std::ffi::OsStr>| {
+        Value::string(part.map(|part| part.to_string_lossy()).unwrap_or_default())
+    };
+
+    let mut dict = DataDict::default();
+    dict.insert(
+        "parent",
+        Value::path(path.parent().unwrap_or_else(|| Path::new(""))),
+    );
+    dict.insert("stem", text(path.file_stem()));
+    dict.insert("extension", text(path.extension()));
+    dict
+}
+
+// as basename(1), / stays itself
+fn basename(path: &Path) -> String {
+    match path.file_name() {
+        Some(name) => name.to_string_lossy().into_owned(),
+        None => path.to_string_lossy().into_owned(),
+    }
+}
+
+// as dirname(1), a bare file name is in .
+fn dirname(path: &Path) -> &Path {
+    match path.parent() {
+        Some(parent) if parent.as_os_str().is_empty() => Path::new("."),
+        Some(parent) => parent,
+        None => path,
+    }
+}
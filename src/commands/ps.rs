@@ -1,27 +1,123 @@
+use std::{
+    collections::{HashMap, HashSet},
+    thread,
+    time::{Duration, Instant},
+};
+
+use users::UsersCache;
+
 use crate::{
     error::ShellError,
-    types::{primary::Value, process::{process_dict}},
+    types::{
+        datadict::DataDict,
+        primary::Value,
+        process::{list_processes, process_dict, ProcClock},
+    },
 };
-use sysinfo::SystemExt;
 
 use super::{Args, Command};
 
+// long enough for busy processes to register a few clock ticks
+const CPU_SAMPLE: Duration = Duration::from_millis(200);
+
 pub struct Ps;
 
 impl Command for Ps {
-    fn run(&self, _args: Args) -> Result<Value, ShellError> {
-        let system = sysinfo::System::new();
-        let process_list: Vec<sysinfo::Process> =
-            system.get_process_list().values().cloned().collect();
+    fn run(&self, args: Args) -> Result<Value, ShellError> {
+        let mut long = false;
+        let mut tree = false;
+        for arg in &args.args {
+            match arg.to_string().as_str() {
+                "--long" | "-l" => long = true,
+                "--tree" | "-t" => tree = true,
+                other => return Err(ShellError::new(format!("ps : unknown argument {}", other))),
+            }
+        }
+
+        let clock = ProcClock::read()?;
+
+        // cpu usage is the cpu time spent between two samples, only taken for ps --long
+        let first_sample: Option<HashMap<i64, u64>> = match long {
+            true => Some(
+                list_processes()?
+                    .into_iter()
+                    .map(|process| (process.pid, process.cpu_ticks))
+                    .collect(),
+            ),
+            false => None,
+        };
+        let started = Instant::now();
+        if first_sample.is_some() {
+            thread::sleep(CPU_SAMPLE);
+        }
+        let processes = list_processes()?;
+        let elapsed = started.elapsed().as_secs_f64();
+
+        let owners = match long {
+            true => Some(UsersCache::new()),
+            false => None,
+        };
 
         let mut process_entries = vec![];
+        let mut pids = vec![];
+
+        for process in &processes {
+            let cpu = first_sample.as_ref().map(|first_sample| {
+                let before = first_sample
+                    .get(&process.pid)
+                    .copied()
+                    .unwrap_or(process.cpu_ticks);
+                let ticks = process.cpu_ticks.saturating_sub(before);
+                ticks as f64 / clock.ticks_per_sec as f64 / elapsed * 100.0
+            });
 
-        for process in process_list {
-            let dict = process_dict(process);
-            let entry = Value::object(dict?);
-            process_entries.push(entry);
+            let dict = process_dict(process, cpu, &clock, owners.as_ref());
+            process_entries.push(dict?);
+            pids.push((process.pid, process.ppid));
         }
 
-        Ok(Value::list(process_entries))
+        match tree {
+            true => Ok(Value::list(build_tree(process_entries, &pids))),
+            false => Ok(Value::list(
+                process_entries
+                    .into_iter()
+                    .map(Value::object)
+                    .collect::<Vec<_>>(),
+            )),
+        }
     }
 }
+
+// Nests every process under its parent in a children column,
+// processes whose parent is not listed are roots
+fn build_tree(entries: Vec<DataDict>, pids: &[(i64, i64)]) -> Vec<Value> {
+    let listed: HashSet<i64> = pids.iter().map(|(pid, _)| *pid).collect();
+
+    let mut children: HashMap<i64, Vec<(i64, DataDict)>> = HashMap::new();
+    let mut roots = vec![];
+    for (dict, (pid, ppid)) in entries.into_iter().zip(pids) {
+        match *ppid != *pid && listed.contains(ppid) {
+            true => children.entry(*ppid).or_default().push((*pid, dict)),
+            false => roots.push((*pid, dict)),
+        }
+    }
+
+    fn attach(
+        (pid, mut dict): (i64, DataDict),
+        children: &mut HashMap<i64, Vec<(i64, DataDict)>>,
+    ) -> Value {
+        let nested = children
+            .remove(&pid)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|child| attach(child, children))
+            .collect::<Vec<_>>();
+        dict.insert("children", Value::list(nested));
+        Value::object(dict)
+    }
+
+    roots
+        .into_iter()
+        .map(|root| attach(root, &mut children))
+        .collect()
+}
@@ -0,0 +1,97 @@
+use crate::{
+    error::ShellError,
+    types::primary::{range_values, Primitive, Value},
+};
+
+use super::{list_input, Args, Command};
+
+#[derive(Debug)]
+pub struct Range;
+
+impl Command for Range {
+    fn run(&self, args: Args) -> Result<Value, ShellError> {
+        let objects = list_input("range", args.instream)?;
+
+        // 10..20 is never expanded, only the indices that exist are visited
+        let indices: Vec<i64> = match args.args.first() {
+            Some(Value::Primitive(Primitive::Range(start, end))) => {
+                // the range is cut to the indices that exist, keeping its direction
+                let low = (*start).min(*end).max(0);
+                let high = (*start).max(*end).min(objects.len() as i64 - 1);
+                match (low <= high, start <= end) {
+                    (false, _) => vec![],
+                    (true, true) => range_values(low, high).collect(),
+                    (true, false) => range_values(high, low).collect(),
+                }
+            }
+            Some(Value::List(indices)) => indices
+                .iter()
+                .map(|index| index.to_int())
+                .collect::<Result<_, _>>()?,
+            _ => return Err(ShellError::new("range expects a range, e.g. range 10..20")),
+        };
+
+        let mut selected = vec![];
+        for index in indices {
+            let item = usize::try_from(index)
+                .ok()
+                .and_then(|index| objects.get(index));
+            // indices past the end are ignored, like slicing
+            if let Some(item) = item {
+                selected.push(item.clone());
+            }
+        }
+
+        Ok(Value::list(selected))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::eval_with;
+
+    fn range(line: &str) -> Value {
+        eval_with("range", Range, line).unwrap()
+    }
+
+    fn strings(items: &[&str]) -> Value {
+        Value::list(
+            items
+                .iter()
+                .map(|item| Value::string(*item))
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    #[test]
+    fn past_the_end_is_empty() {
+        assert_eq!(range("[a b c d e] | range 10..20"), strings(&[]));
+        assert_eq!(range("[a b c d e] | range 20..10"), strings(&[]));
+        assert_eq!(range("[] | range 0..3"), strings(&[]));
+    }
+
+    #[test]
+    fn partly_overlapping() {
+        assert_eq!(range("[a b c d e] | range 3..10"), strings(&["d", "e"]));
+        assert_eq!(range("[a b c d e] | range -2..1"), strings(&["a", "b"]));
+    }
+
+    #[test]
+    fn reversed() {
+        assert_eq!(
+            range("[a b c d e] | range 10..2"),
+            strings(&["e", "d", "c"])
+        );
+        assert_eq!(range("[a b c d e] | range 1..0"), strings(&["b", "a"]));
+    }
+
+    #[test]
+    fn tables_and_index_lists() {
+        assert_eq!(
+            range("[{a: 1} {a: 2} {a: 3}] | range 1..5"),
+            range("[{a: 2} {a: 3}]")
+        );
+        assert_eq!(range("[a b c] | range [2 0 7]"), strings(&["c", "a"]));
+    }
+}
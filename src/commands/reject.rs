@@ -0,0 +1,23 @@
+use crate::{error::ShellError, types::primary::Value};
+
+use super::{map_records, Args, Command};
+
+#[derive(Debug)]
+pub struct Reject;
+
+impl Command for Reject {
+    fn run(&self, args: Args) -> Result<Value, ShellError> {
+        if args.args.is_empty() {
+            return Err(ShellError::new("reject expects column names"));
+        }
+
+        let columns: Vec<String> = args.args.iter().map(|arg| arg.to_string()).collect();
+
+        map_records("reject", args.instream, |mut row| {
+            for column in &columns {
+                row.remove(column);
+            }
+            Ok(row)
+        })
+    }
+}
@@ -0,0 +1,36 @@
+use crate::{error::ShellError, types::primary::Value};
+
+use super::{map_records, Args, Command};
+
+#[derive(Debug)]
+pub struct Rename;
+
+impl Command for Rename {
+    fn run(&self, args: Args) -> Result<Value, ShellError> {
+        let names: Vec<String> = args.args.iter().map(|arg| arg.to_string()).collect();
+        let pairs = names.chunks_exact(2);
+
+        if names.is_empty() || !pairs.remainder().is_empty() {
+            return Err(ShellError::new(
+                "rename expects pairs of columns, e.g. rename size bytes",
+            ));
+        }
+
+        map_records("rename", args.instream, |mut row| {
+            for pair in pairs.clone() {
+                if row.get(&pair[0]).is_none() {
+                    return Err(ShellError::new(format!("no column named {}", pair[0])));
+                }
+                // renaming onto another column would silently drop it
+                if pair[0] != pair[1] && row.get(&pair[1]).is_some() {
+                    return Err(ShellError::new(format!(
+                        "rename : column {} already exists",
+                        pair[1]
+                    )));
+                }
+                row.rename(&pair[0], &pair[1]);
+            }
+            Ok(row)
+        })
+    }
+}
@@ -0,0 +1,70 @@
+use std::fs;
+
+use crate::{error::ShellError, types::primary::Value};
+
+use super::{
+    fileops::{change_row, expand_paths, piped_paths, FileArgs},
+    trash::move_to_trash,
+    Args, Command,
+};
+
+#[derive(Debug)]
+pub struct Rm;
+
+impl Command for Rm {
+    fn run(&self, args: Args) -> Result<Value, ShellError> {
+        let file_args = FileArgs::parse(
+            "rm",
+            &args.args,
+            &[
+                ("recursive", 'r'),
+                ("force", 'f'),
+                ("permanent", 'p'),
+                ("dry-run", 'n'),
+            ],
+        )?;
+        let recursive = file_args.has("recursive");
+        let force = file_args.has("force");
+        let permanent = file_args.has("permanent");
+        let dry_run = file_args.has("dry-run");
+
+        // files go to the trash unless --permanent, see trash list and trash restore
+        let action = if permanent { "removed" } else { "trashed" };
+
+        // ls | filter f -> f.size == 0 | rm
+        let mut paths = piped_paths("rm", args.instream)?;
+        paths.extend(expand_paths("rm", &file_args.paths)?);
+        if paths.is_empty() && !force {
+            return Err(ShellError::new("rm expects a path"));
+        }
+
+        let mut changes = vec![];
+        for path in paths {
+            // symlinks are removed themselves, never what they point to
+            let metadata = match fs::symlink_metadata(&path) {
+                Ok(metadata) => metadata,
+                Err(_) if force => continue,
+                Err(err) => {
+                    changes.push(change_row(action, &path, None, Err(err.into()), false));
+                    continue;
+                }
+            };
+
+            let result = if metadata.is_dir() && !recursive {
+                Err(ShellError::new("is a directory, use -r to remove it"))
+            } else if dry_run {
+                Ok(())
+            } else if !permanent {
+                move_to_trash(&path)
+            } else if metadata.is_dir() {
+                fs::remove_dir_all(&path).map_err(ShellError::from)
+            } else {
+                fs::remove_file(&path).map_err(ShellError::from)
+            };
+            let dry_run = dry_run && result.is_ok();
+            changes.push(change_row(action, &path, None, result, dry_run));
+        }
+
+        Ok(Value::list(changes))
+    }
+}
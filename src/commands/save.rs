@@ -0,0 +1,49 @@
+use std::fs;
+
+use crate::{
+    error::ShellError,
+    formats::Format,
+    paths::expand_path,
+    stream::RushStream,
+    types::primary::{Primitive, Value},
+};
+
+use super::{Args, Command};
+
+#[derive(Debug)]
+pub struct Save;
+
+impl Command for Save {
+    fn run(&self, args: Args) -> Result<Value, ShellError> {
+        let path_arg = match args.args.first() {
+            Some(path) => path.to_string(),
+            None => return Err(ShellError::new("save expects a file path")),
+        };
+        let path = expand_path(&path_arg);
+
+        let value = match args.instream {
+            RushStream::Internal(value) => value,
+            RushStream::External(_) => {
+                return Err(ShellError::new("external streams not supported yet"))
+            }
+            RushStream::None => return Err(ShellError::new("save expects an input")),
+        };
+
+        // text is written as is, so open --raw | save round trips
+        let contents = match (&value, Format::from_path(&path)) {
+            (Value::Primitive(Primitive::String(text)), _) => text.clone(),
+            (_, Some(format)) => format.serialize(&value)?,
+            (_, None) => {
+                return Err(ShellError::new(format!(
+                    "save : unknown format for {}, convert with to <format> first",
+                    path_arg
+                )))
+            }
+        };
+
+        fs::write(&path, contents)
+            .map_err(|err| ShellError::new(format!("save : {} : {}", path_arg, err)))?;
+
+        Ok(Value::none())
+    }
+}
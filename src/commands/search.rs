@@ -0,0 +1,152 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::mpsc,
+};
+
+use ignore::{WalkBuilder, WalkState};
+use regex::{Regex, RegexBuilder};
+
+use crate::{
+    error::ShellError,
+    interrupt,
+    paths::expand_path,
+    types::{datadict::DataDict, primary::Value},
+};
+
+use super::{Args, Command};
+
+// files with a nul byte this early on are taken as binary
+const BINARY_PROBE: usize = 8192;
+
+#[derive(Debug)]
+pub struct Search;
+
+struct Match {
+    file: PathBuf,
+    line: usize,
+    column: usize,
+    text: String,
+}
+
+impl Command for Search {
+    fn run(&self, args: Args) -> Result<Value, ShellError> {
+        let mut fixed = false;
+        let mut ignore_case = false;
+        let mut hidden = false;
+        let mut params = vec![];
+
+        for arg in &args.args {
+            match arg.to_string().as_str() {
+                "--fixed" | "-F" => fixed = true,
+                "--ignore-case" | "-i" => ignore_case = true,
+                "--all" | "-a" => hidden = true,
+                flag if flag.starts_with("--") => {
+                    return Err(ShellError::new(format!("search : unknown flag {}", flag)))
+                }
+                param => params.push(param.to_string()),
+            }
+        }
+
+        if params.is_empty() {
+            return Err(ShellError::new(
+                "search expects a pattern, e.g. search fn src",
+            ));
+        }
+        let pattern = params.remove(0);
+        let pattern = match fixed {
+            true => regex::escape(&pattern),
+            false => pattern,
+        };
+        let regex = RegexBuilder::new(&pattern)
+            .case_insensitive(ignore_case)
+            .build()
+            .map_err(|err| ShellError::new(format!("search : invalid regex : {}", err)))?;
+
+        // without a path the working directory is searched and names are relative
+        let bare = params.is_empty();
+        if bare {
+            params.push(".".to_string());
+        }
+        let roots: Vec<PathBuf> = params.iter().map(|root| expand_path(root)).collect();
+
+        // .gitignore and hidden files are respected as in most code search tools
+        let mut builder = WalkBuilder::new(&roots[0]);
+        for root in &roots[1..] {
+            builder.add(root);
+        }
+        builder.hidden(!hidden);
+
+        let (sender, receiver) = mpsc::channel();
+        builder.build_parallel().run(|| {
+            let sender = sender.clone();
+            let regex = regex.clone();
+            Box::new(move |result| {
+                if interrupt::interrupted() {
+                    return WalkState::Quit;
+                }
+                let entry = match result {
+                    Ok(entry) if entry.file_type().is_some_and(|kind| kind.is_file()) => entry,
+                    _ => return WalkState::Continue,
+                };
+                let file = match bare {
+                    true => entry.path().strip_prefix(".").unwrap_or(entry.path()),
+                    false => entry.path(),
+                };
+                for found in search_file(entry.path(), file, &regex) {
+                    let _ = sender.send(found);
+                }
+                WalkState::Continue
+            })
+        });
+        drop(sender);
+
+        if interrupt::interrupted() {
+            return Err(ShellError::new("search : interrupted"));
+        }
+
+        // parallel walks finish in any order
+        let mut matches: Vec<Match> = receiver.into_iter().collect();
+        matches.sort_by(|a, b| (&a.file, a.line).cmp(&(&b.file, b.line)));
+
+        let rows = matches
+            .into_iter()
+            .map(|found| {
+                let mut dict = DataDict::default();
+                dict.insert("file", Value::path(found.file));
+                dict.insert("line", Value::int(found.line as i64));
+                dict.insert("column", Value::int(found.column as i64));
+                dict.insert("text", Value::string(found.text));
+                Value::object(dict)
+            })
+            .collect::<Vec<_>>();
+
+        Ok(Value::list(rows))
+    }
+}
+
+// One match per line, lines and columns count from one
+fn search_file(path: &Path, file: &Path, regex: &Regex) -> Vec<Match> {
+    let bytes = match fs::read(path) {
+        Ok(bytes) => bytes,
+        Err(_) => return vec![],
+    };
+    if bytes.iter().take(BINARY_PROBE).any(|byte| *byte == 0) {
+        return vec![];
+    }
+
+    let contents = String::from_utf8_lossy(&bytes);
+    contents
+        .lines()
+        .enumerate()
+        .filter_map(|(index, line)| {
+            let found = regex.find(line)?;
+            Some(Match {
+                file: file.to_path_buf(),
+                line: index + 1,
+                column: line[..found.start()].chars().count() + 1,
+                text: line.trim_end().to_string(),
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,31 @@
+use crate::{
+    error::ShellError,
+    types::{datadict::DataDict, primary::Value},
+};
+
+use super::{map_records, Args, Command};
+
+#[derive(Debug)]
+pub struct Select;
+
+impl Command for Select {
+    fn run(&self, args: Args) -> Result<Value, ShellError> {
+        if args.args.is_empty() {
+            return Err(ShellError::new("select expects column names"));
+        }
+
+        let columns: Vec<String> = args.args.iter().map(|arg| arg.to_string()).collect();
+
+        // columns come out in the order they were asked for
+        map_records("select", args.instream, |row| {
+            let mut selected = DataDict::default();
+            for column in &columns {
+                match row.get(column) {
+                    Some(value) => selected.insert(column, value.clone()),
+                    None => return Err(ShellError::new(format!("no column named {}", column))),
+                }
+            }
+            Ok(selected)
+        })
+    }
+}
@@ -0,0 +1,66 @@
+use crate::{error::ShellError, types::primary::Value};
+
+use super::{count_arg, list_input, Args, Command};
+
+#[derive(Debug)]
+pub struct Skip;
+
+impl Command for Skip {
+    fn run(&self, args: Args) -> Result<Value, ShellError> {
+        let mut objects = list_input("skip", args.instream)?;
+
+        match args.args.as_slice() {
+            [word, Value::Lambda(lambda)] if word.to_string() == "while" => {
+                let mut skipped = 0;
+                for object in &objects {
+                    if !lambda.call(args.ctx, object.clone())?.to_bool()? {
+                        break;
+                    }
+                    skipped += 1;
+                }
+                objects.drain(..skipped);
+                Ok(Value::list(objects))
+            }
+            [] | [_] => {
+                let count = count_arg("skip", args.args.first())?;
+                objects.drain(..count.min(objects.len()));
+                Ok(Value::list(objects))
+            }
+            _ => Err(ShellError::new(
+                "skip expects a number or a condition, e.g. skip 5 or skip while f -> f.size > 1KB",
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::eval_with;
+
+    fn skip(line: &str) -> Value {
+        eval_with("skip", Skip, line).unwrap()
+    }
+
+    #[test]
+    fn counts() {
+        assert_eq!(skip("[1 2 3] | skip"), skip("[2 3]"));
+        assert_eq!(skip("[1 2 3] | skip 2"), skip("[3]"));
+        assert_eq!(skip("[1 2 3] | skip 5"), skip("[]"));
+    }
+
+    #[test]
+    fn skip_while() {
+        assert_eq!(skip("[1 2 3 1] | skip while x -> x < 3"), skip("[3 1]"));
+        assert_eq!(skip("[1 2 3] | skip while x -> x > 5"), skip("[1 2 3]"));
+        assert_eq!(skip("[1 2 3] | skip while x -> x > 0"), skip("[]"));
+    }
+
+    #[test]
+    fn tables() {
+        assert_eq!(
+            skip("[{a: 1} {a: 5}] | skip while r -> r.a < 3"),
+            skip("[{a: 5}]")
+        );
+    }
+}
@@ -1,17 +1,27 @@
-use crate::{error::ShellError, stream::RushStream, types::primary::Value};
+use std::cmp::Ordering;
+
+use crate::{error::ShellError, stream::RushStream, types::primary::Value, utils::natural_cmp};
 
 use super::{Args, Command};
 
 #[derive(Debug)]
 pub struct SortBy;
 
+struct SortKey {
+    path: String,
+    descending: bool,
+}
+
+#[derive(Default)]
+struct SortOptions {
+    reverse: bool,
+    ignore_case: bool,
+    natural: bool,
+}
+
 impl Command for SortBy {
     fn run(&self, args: Args) -> Result<Value, ShellError> {
-        if args.args.is_empty() {
-            return Err(ShellError::new("No sortby field provided"));
-        }
-
-        let mut objects = match args.instream {
+        let objects = match args.instream {
             RushStream::Internal(Value::List(list)) => list,
             RushStream::External(_) => {
                 return Err(ShellError::new("external streams not supported yet"))
@@ -19,18 +29,110 @@ impl Command for SortBy {
             _ => return Err(ShellError::new("sortby expects a list of objects")),
         };
 
-        let sort_key = &args.args[0].to_string();
+        let mut options = SortOptions::default();
+        let mut keys = vec![];
+
+        // sortby size name:desc --natural
+        for arg in &args.args {
+            match arg.to_string().as_str() {
+                "--reverse" | "-r" => options.reverse = true,
+                "--ignore-case" | "-i" => options.ignore_case = true,
+                "--natural" | "-n" => options.natural = true,
+                key => {
+                    let (path, descending) = match key.rsplit_once(':') {
+                        Some((path, "desc")) => (path, true),
+                        Some((path, "asc")) => (path, false),
+                        _ => (key, false),
+                    };
+                    keys.push(SortKey {
+                        path: path.to_string(),
+                        descending,
+                    });
+                }
+            }
+        }
+
+        // keys are looked up up front, so a missing column is an error rather than a silent tie
+        let mut rows = objects
+            .into_iter()
+            .map(|object| {
+                let sort_values = match keys.is_empty() {
+                    // without a key, a list of primitives is sorted by value
+                    true => vec![object.clone()],
+                    false => keys
+                        .iter()
+                        .map(|key| lookup(&object, &key.path))
+                        .collect::<Result<Vec<_>, _>>()?,
+                };
+                Ok((sort_values, object))
+            })
+            .collect::<Result<Vec<_>, ShellError>>()?;
 
-        objects.sort_by(|a, b| {
-            let a = a.get_data_from_key(sort_key);
-            let b = b.get_data_from_key(sort_key);
+        // sort_by is stable, rows with equal keys keep their order
+        rows.sort_by(|(a, _), (b, _)| {
+            let ordering = a
+                .iter()
+                .zip(b)
+                .enumerate()
+                .map(|(i, (a, b))| {
+                    let ordering = compare_values(a, b, &options);
+                    match keys.get(i) {
+                        Some(key) if key.descending => ordering.reverse(),
+                        _ => ordering,
+                    }
+                })
+                .find(|ordering| ordering.is_ne())
+                .unwrap_or(Ordering::Equal);
 
-            match (a, b) {
-                (Value::Primitive(a), Value::Primitive(b)) => a.cmp(b),
-                _ => std::cmp::Ordering::Equal,
+            match options.reverse {
+                true => ordering.reverse(),
+                false => ordering,
             }
         });
 
-        Ok(Value::list(objects))
+        Ok(Value::list(
+            rows.into_iter()
+                .map(|(_, object)| object)
+                .collect::<Vec<_>>(),
+        ))
     }
 }
+
+fn lookup(object: &Value, path: &str) -> Result<Value, ShellError> {
+    let mut value = object.clone();
+    for field in path.split('.') {
+        value = value
+            .get_field(field)
+            .map_err(|_| ShellError::new(format!("sortby : no column named {}", path)))?;
+    }
+    Ok(value)
+}
+
+fn compare_values(a: &Value, b: &Value, options: &SortOptions) -> Ordering {
+    let text = match (a, b) {
+        (Value::Primitive(a), Value::Primitive(b)) => a.as_str().zip(b.as_str()),
+        _ => None,
+    };
+
+    let (a_text, b_text) = match text {
+        Some(text) => text,
+        None => return a.cmp(b),
+    };
+
+    let folded = match options.ignore_case {
+        true => Some((a_text.to_lowercase(), b_text.to_lowercase())),
+        false => None,
+    };
+    let (x, y) = match &folded {
+        Some((x, y)) => (x.as_str(), y.as_str()),
+        None => (a_text.as_ref(), b_text.as_ref()),
+    };
+
+    let ordering = match options.natural {
+        true => natural_cmp(x, y),
+        false => x.cmp(y),
+    };
+
+    // ties between differently cased strings still need a consistent order
+    ordering.then_with(|| a_text.cmp(&b_text))
+}
@@ -0,0 +1,81 @@
+use regex::Regex;
+
+use crate::{
+    error::ShellError,
+    stream::RushStream,
+    types::{datadict::DataDict, primary::Value},
+};
+
+use super::{strings::as_text, Args, Command};
+
+#[derive(Debug)]
+pub struct Split;
+
+impl Command for Split {
+    fn run(&self, args: Args) -> Result<Value, ShellError> {
+        let mut params: Vec<String> = args.args.iter().map(|arg| arg.to_string()).collect();
+
+        let regex = match params.iter().position(|p| p == "--regex" || p == "-r") {
+            Some(index) => {
+                params.remove(index);
+                true
+            }
+            None => false,
+        };
+
+        let (mode, separator, names) = match params.as_slice() {
+            [mode, separator, names @ ..] => (mode.as_str(), separator, names),
+            _ => {
+                return Err(ShellError::new(
+                    "split expects row or column and a separator, e.g. split row \",\"",
+                ))
+            }
+        };
+
+        let separator = match regex {
+            true => Regex::new(separator),
+            false => Regex::new(&regex::escape(separator)),
+        }
+        .map_err(|err| ShellError::new(format!("split : invalid regex : {}", err)))?;
+
+        let input = match args.instream {
+            RushStream::Internal(Value::List(items)) => items,
+            RushStream::Internal(value) => vec![value],
+            RushStream::External(_) => {
+                return Err(ShellError::new("external streams not supported yet"))
+            }
+            RushStream::None => return Err(ShellError::new("split expects an input")),
+        };
+
+        let mut split = vec![];
+        for item in &input {
+            let text = as_text(item).ok_or_else(|| {
+                ShellError::new(format!("split expects strings, found {}", item.type_name()))
+            })?;
+            let parts = separator.split(&text).map(Value::string);
+
+            match mode {
+                // split row flattens, so a list of lines becomes a list of fields
+                "row" => split.extend(parts),
+                "column" => {
+                    let mut row = DataDict::default();
+                    for (i, part) in parts.enumerate() {
+                        let name = match names.get(i) {
+                            Some(name) => name.clone(),
+                            None => format!("column{}", i + 1),
+                        };
+                        row.insert(name, part);
+                    }
+                    split.push(Value::object(row));
+                }
+                _ => return Err(ShellError::new(format!("split : unknown mode {}", mode))),
+            }
+        }
+
+        // a single string split into columns is a record
+        match (mode, input.len()) {
+            ("column", 1) => Ok(split.swap_remove(0)),
+            _ => Ok(Value::list(split)),
+        }
+    }
+}
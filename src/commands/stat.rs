@@ -0,0 +1,88 @@
+use std::{
+    fs,
+    os::unix::fs::{MetadataExt, PermissionsExt},
+    path::PathBuf,
+};
+
+use users::UsersCache;
+
+use crate::{
+    error::ShellError,
+    types::{
+        datadict::DataDict,
+        direntry::{group_name, mode_string, owner_name, time_or_none, type_name},
+        primary::Value,
+    },
+};
+
+use super::{
+    fileops::{expand_paths, piped_paths, FileArgs},
+    Args, Command,
+};
+
+#[derive(Debug)]
+pub struct Stat;
+
+impl Command for Stat {
+    fn run(&self, args: Args) -> Result<Value, ShellError> {
+        let file_args = FileArgs::parse("stat", &args.args, &[])?;
+        let mut paths = piped_paths("stat", args.instream)?;
+        paths.extend(expand_paths("stat", &file_args.paths)?);
+
+        let owners = UsersCache::new();
+        let mut records = paths
+            .iter()
+            .map(|path| {
+                let metadata = fs::metadata(path).map_err(|err| {
+                    ShellError::new(format!("stat : {} : {}", path.display(), err))
+                })?;
+                Ok(Value::object(stat_dict(path.clone(), &metadata, &owners)))
+            })
+            .collect::<Result<Vec<_>, ShellError>>()?;
+
+        // a single path is a record, several are a table
+        match records.len() {
+            0 => Err(ShellError::new("stat expects a path")),
+            1 => Ok(records.remove(0)),
+            _ => Ok(Value::list(records)),
+        }
+    }
+}
+
+fn stat_dict(path: PathBuf, metadata: &fs::Metadata, owners: &UsersCache) -> DataDict {
+    let mut dict = DataDict::default();
+    let file_type = metadata.file_type();
+
+    dict.insert("path", Value::path(path));
+    dict.insert("type", Value::string(type_name(&file_type)));
+    dict.insert("size", Value::size(metadata.len()));
+    dict.insert("mode", Value::string(mode_string(metadata)));
+    dict.insert(
+        "octal",
+        Value::string(format!("{:o}", metadata.permissions().mode() & 0o7777)),
+    );
+    dict.insert("readonly", Value::bool(metadata.permissions().readonly()));
+    dict.insert("uid", Value::int(metadata.uid() as i64));
+    dict.insert("gid", Value::int(metadata.gid() as i64));
+    dict.insert("owner", Value::string(owner_name(owners, metadata.uid())));
+    dict.insert("group", Value::string(group_name(owners, metadata.gid())));
+    dict.insert("inode", Value::int(metadata.ino() as i64));
+    dict.insert("device", Value::int(metadata.dev() as i64));
+    dict.insert("links", Value::int(metadata.nlink() as i64));
+    dict.insert("blocks", Value::int(metadata.blocks() as i64));
+    dict.insert("block_size", Value::size(metadata.blksize()));
+    dict.insert("accessed", time_or_none(metadata.accessed()));
+    dict.insert("modified", time_or_none(metadata.modified()));
+    dict.insert("created", time_or_none(metadata.created()));
+    // ctime is when the metadata itself last changed
+    let changed = std::time::UNIX_EPOCH
+        .checked_add(std::time::Duration::new(
+            metadata.ctime().max(0) as u64,
+            metadata.ctime_nsec().max(0) as u32,
+        ))
+        .map(Value::time)
+        .unwrap_or_else(Value::none);
+    dict.insert("changed", changed);
+
+    dict
+}
@@ -0,0 +1,190 @@
+use regex::Regex;
+
+use crate::{
+    error::ShellError,
+    stream::RushStream,
+    types::primary::{Primitive, Value},
+};
+
+use super::{Args, Command};
+
+#[derive(Debug)]
+pub struct Str;
+
+impl Command for Str {
+    fn run(&self, args: Args) -> Result<Value, ShellError> {
+        let mut params = args.args;
+
+        // replace takes --all, it can go anywhere after the operation
+        let all = match params
+            .iter()
+            .position(|p| matches!(p.to_string().as_str(), "--all" | "-a"))
+        {
+            Some(index) => {
+                params.remove(index);
+                true
+            }
+            None => false,
+        };
+
+        if params.is_empty() {
+            return Err(ShellError::new("str expects an operation, e.g. str upcase"));
+        }
+        let operation = params.remove(0).to_string();
+
+        let arity = match operation.as_str() {
+            "upcase" | "downcase" | "trim" | "length" => 0,
+            "contains" | "starts-with" | "ends-with" | "substring" => 1,
+            "replace" => 2,
+            _ => {
+                return Err(ShellError::new(format!(
+                    "str : unknown operation {}",
+                    operation
+                )))
+            }
+        };
+
+        if params.len() < arity {
+            return Err(ShellError::new(format!(
+                "str {} expects {} argument(s)",
+                operation, arity
+            )));
+        }
+        // whatever follows the operation's own arguments are columns
+        let columns: Vec<String> = params
+            .split_off(arity)
+            .iter()
+            .map(|c| c.to_string())
+            .collect();
+
+        let input = match args.instream {
+            RushStream::Internal(value) => value,
+            RushStream::External(_) => {
+                return Err(ShellError::new("external streams not supported yet"))
+            }
+            RushStream::None => return Err(ShellError::new("str expects an input")),
+        };
+
+        let command = format!("str {}", operation);
+        match operation.as_str() {
+            "upcase" => map_strings(&command, input, &columns, &|s| {
+                Ok(Value::string(s.to_uppercase()))
+            }),
+            "downcase" => map_strings(&command, input, &columns, &|s| {
+                Ok(Value::string(s.to_lowercase()))
+            }),
+            "trim" => map_strings(&command, input, &columns, &|s| Ok(Value::string(s.trim()))),
+            "length" => map_strings(&command, input, &columns, &|s| {
+                Ok(Value::int(s.chars().count() as i64))
+            }),
+            "contains" => {
+                let needle = params[0].to_string();
+                map_strings(&command, input, &columns, &|s| {
+                    Ok(Value::bool(s.contains(&needle)))
+                })
+            }
+            "starts-with" => {
+                let prefix = params[0].to_string();
+                map_strings(&command, input, &columns, &|s| {
+                    Ok(Value::bool(s.starts_with(&prefix)))
+                })
+            }
+            "ends-with" => {
+                let suffix = params[0].to_string();
+                map_strings(&command, input, &columns, &|s| {
+                    Ok(Value::bool(s.ends_with(&suffix)))
+                })
+            }
+            "replace" => {
+                let pattern = Regex::new(&params[0].to_string()).map_err(|err| {
+                    ShellError::new(format!("str replace : invalid regex : {}", err))
+                })?;
+                let replacement = params[1].to_string();
+                let limit = if all { 0 } else { 1 };
+                map_strings(&command, input, &columns, &|s| {
+                    Ok(Value::string(pattern.replacen(
+                        s,
+                        limit,
+                        replacement.as_str(),
+                    )))
+                })
+            }
+            _ => {
+                let (start, end) = substring_bounds(&params[0])?;
+                map_strings(&command, input, &columns, &|s| {
+                    let substring = s
+                        .chars()
+                        .skip(start)
+                        .take(end.saturating_sub(start))
+                        .collect::<String>();
+                    Ok(Value::string(substring))
+                })
+            }
+        }
+    }
+}
+
+// substring 2 is from the third character on, substring 0..4 the first five
+fn substring_bounds(arg: &Value) -> Result<(usize, usize), ShellError> {
+    let bounds = match arg {
+        Value::Primitive(Primitive::Range(start, end)) => (*start, end.saturating_add(1)),
+        Value::List(range) => match (range.first(), range.last()) {
+            (Some(start), Some(end)) => (start.to_int()?, end.to_int()?.saturating_add(1)),
+            _ => return Err(ShellError::new("str substring : empty range")),
+        },
+        _ => (arg.to_int()?, i64::MAX),
+    };
+
+    match (usize::try_from(bounds.0), usize::try_from(bounds.1)) {
+        (Ok(start), Ok(end)) => Ok((start, end)),
+        (Ok(start), Err(_)) => Ok((start, usize::MAX)),
+        _ => Err(ShellError::new("str substring expects positive indices")),
+    }
+}
+
+/// Applies `f` to a string, every string of a list, or the named columns of a table
+pub fn map_strings(
+    command: &str,
+    value: Value,
+    columns: &[String],
+    f: &dyn Fn(&str) -> Result<Value, ShellError>,
+) -> Result<Value, ShellError> {
+    match value {
+        Value::List(items) => items
+            .into_iter()
+            .map(|item| map_strings(command, item, columns, f))
+            .collect::<Result<Vec<_>, _>>()
+            .map(Value::list),
+        Value::Object(mut dict) if !columns.is_empty() => {
+            for column in columns {
+                let cell = match dict.get(column) {
+                    Some(cell) => cell.clone(),
+                    None => return Err(ShellError::new(format!("no column named {}", column))),
+                };
+                dict.insert(column, map_strings(command, cell, &[], f)?);
+            }
+            Ok(Value::Object(dict))
+        }
+        Value::Object(_) => Err(ShellError::new(format!(
+            "{} expects column names for a table, e.g. {} name",
+            command, command
+        ))),
+        Value::Primitive(ref primitive) => match primitive.as_str() {
+            Some(text) => f(&text),
+            None => Err(ShellError::new(format!(
+                "{} expects a string, found {}",
+                command,
+                value.type_name()
+            ))),
+        },
+        Value::Lambda(_) => Err(ShellError::new(format!("{} expects a string", command))),
+    }
+}
+
+/// Text of a string or path value
+pub fn as_text(value: &Value) -> Option<String> {
+    match value {
+        Value::Primitive(primitive) => primitive.as_str().map(|text| text.into_owned()),
+        _ => None,
+    }
+}
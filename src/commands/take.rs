@@ -1,27 +1,72 @@
-use crate::{error::ShellError, stream::RushStream, types::primary::Value};
+use crate::{error::ShellError, types::primary::Value};
 
-use super::{Args, Command};
+use super::{list_input, Args, Command};
 
 #[derive(Debug)]
 pub struct Take;
 
 impl Command for Take {
     fn run(&self, args: Args) -> Result<Value, ShellError> {
-        if args.args.is_empty() {
-            return Err(ShellError::new("No take number provided"));
-        }
+        let mut objects = list_input("take", args.instream)?;
 
-        let mut objects = match args.instream {
-            RushStream::Internal(Value::List(list)) => list,
-            RushStream::External(_) => {
-                return Err(ShellError::new("external streams not supported yet"))
+        match args.args.as_slice() {
+            [word, Value::Lambda(lambda)] if word.to_string() == "while" => {
+                let mut taken = vec![];
+                for object in objects {
+                    if !lambda.call(args.ctx, object.clone())?.to_bool()? {
+                        break;
+                    }
+                    taken.push(object);
+                }
+                Ok(Value::list(taken))
+            }
+            [limit] => {
+                let limit = limit.to_int()?;
+                // take -5 keeps the last five
+                match usize::try_from(limit) {
+                    Ok(limit) => objects.truncate(limit),
+                    Err(_) => {
+                        let start = objects.len().saturating_sub(limit.unsigned_abs() as usize);
+                        objects.drain(..start);
+                    }
+                }
+                Ok(Value::list(objects))
             }
-            _ => return Err(ShellError::new("limit expects a list of objects")),
-        };
+            _ => Err(ShellError::new(
+                "take expects a number or a condition, e.g. take 5 or take while f -> f.size > 1KB",
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::eval_with;
 
-        let limit = &args.args[0].to_int()?;
-        objects.truncate(*limit as usize);
+    fn take(line: &str) -> Value {
+        eval_with("take", Take, line).unwrap()
+    }
+
+    #[test]
+    fn negative_counts_keep_the_end() {
+        assert_eq!(take("[1 2 3 4] | take -2"), take("[3 4]"));
+        assert_eq!(take("[1 2 3] | take -5"), take("[1 2 3]"));
+        assert_eq!(take("[1 2 3] | take 5"), take("[1 2 3]"));
+    }
+
+    #[test]
+    fn take_while() {
+        assert_eq!(take("[1 2 3 1] | take while x -> x < 3"), take("[1 2]"));
+        assert_eq!(take("[1 2 3] | take while x -> x > 5"), take("[]"));
+    }
 
-        Ok(Value::list(objects))
+    #[test]
+    fn tables() {
+        assert_eq!(
+            take("[{a: 1} {a: 2} {a: 3}] | take while r -> r.a != 3"),
+            take("[{a: 1} {a: 2}]")
+        );
+        assert_eq!(take("[{a: 1} {a: 2}] | take -1"), take("[{a: 2}]"));
     }
 }
@@ -0,0 +1,22 @@
+use crate::{error::ShellError, formats::Format, stream::RushStream, types::primary::Value};
+
+use super::{Args, Command};
+
+#[derive(Debug)]
+pub struct ToFormat;
+
+impl Command for ToFormat {
+    fn run(&self, args: Args) -> Result<Value, ShellError> {
+        let format = match args.args.first() {
+            Some(name) => Format::from_name(&name.to_string())
+                .ok_or_else(|| ShellError::new(format!("to : unknown format {}", name)))?,
+            None => return Err(ShellError::new("to expects a format, e.g. to json")),
+        };
+
+        match args.instream {
+            RushStream::Internal(value) => Ok(Value::string(format.serialize(&value)?)),
+            RushStream::External(_) => Err(ShellError::new("external streams not supported yet")),
+            RushStream::None => Err(ShellError::new("to expects an input")),
+        }
+    }
+}
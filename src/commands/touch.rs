@@ -0,0 +1,50 @@
+use std::fs::OpenOptions;
+
+use filetime::FileTime;
+
+use crate::{error::ShellError, paths::expand_path, types::primary::Value};
+
+use super::{
+    fileops::{change_row, piped_paths, FileArgs},
+    Args, Command,
+};
+
+#[derive(Debug)]
+pub struct Touch;
+
+impl Command for Touch {
+    fn run(&self, args: Args) -> Result<Value, ShellError> {
+        let file_args = FileArgs::parse("touch", &args.args, &[("dry-run", 'n')])?;
+        let dry_run = file_args.has("dry-run");
+
+        let mut paths = piped_paths("touch", args.instream)?;
+        paths.extend(file_args.paths.iter().map(|path| expand_path(path)));
+        if paths.is_empty() {
+            return Err(ShellError::new("touch expects a file name"));
+        }
+
+        let mut changes = vec![];
+        for path in paths {
+            // existing files only get their times updated
+            let exists = path.exists();
+            let action = if exists { "touched" } else { "created" };
+
+            let result = match (dry_run, exists) {
+                (true, _) => Ok(()),
+                (false, true) => {
+                    let now = FileTime::now();
+                    filetime::set_file_times(&path, now, now).map_err(ShellError::from)
+                }
+                (false, false) => OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(&path)
+                    .map(|_| ())
+                    .map_err(ShellError::from),
+            };
+            changes.push(change_row(action, &path, None, result, dry_run));
+        }
+
+        Ok(Value::list(changes))
+    }
+}
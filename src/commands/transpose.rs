@@ -0,0 +1,130 @@
+use crate::{
+    error::ShellError,
+    stream::RushStream,
+    types::{datadict::DataDict, primary::Value},
+};
+
+use super::{Args, Command};
+
+#[derive(Debug)]
+pub struct Transpose;
+
+impl Command for Transpose {
+    fn run(&self, args: Args) -> Result<Value, ShellError> {
+        let mut header_row = false;
+        for arg in &args.args {
+            match arg.to_string().as_str() {
+                "--header-row" | "-r" => header_row = true,
+                other => {
+                    return Err(ShellError::new(format!(
+                        "transpose : unexpected argument {}",
+                        other
+                    )))
+                }
+            }
+        }
+
+        match args.instream {
+            RushStream::Internal(Value::Object(dict)) => Ok(record_to_rows(&dict)),
+            RushStream::Internal(Value::List(rows)) => {
+                let rows = rows
+                    .iter()
+                    .map(|row| match row {
+                        Value::Object(dict) => Ok(dict),
+                        other => Err(ShellError::new(format!(
+                            "transpose expects a table, found a list of {}",
+                            other.type_name()
+                        ))),
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                match header_row {
+                    true => columns_to_rows(&rows),
+                    false => Ok(rows_to_columns(&rows)),
+                }
+            }
+            RushStream::External(_) => Err(ShellError::new("external streams not supported yet")),
+            _ => Err(ShellError::new("transpose expects a record or a table")),
+        }
+    }
+}
+
+// {a: 1, b: 2} becomes rows of column and value
+fn record_to_rows(dict: &DataDict) -> Value {
+    let rows = dict
+        .iter()
+        .map(|(name, value)| {
+            let mut row = DataDict::default();
+            row.insert("column", Value::string(name));
+            row.insert("value", value.clone());
+            Value::object(row)
+        })
+        .collect::<Vec<_>>();
+    Value::list(rows)
+}
+
+// Every column becomes a row, with one row0, row1, ... cell per original row
+fn rows_to_columns(rows: &[&DataDict]) -> Value {
+    let mut columns: Vec<&String> = vec![];
+    for row in rows {
+        for (name, _) in row.iter() {
+            if !columns.contains(&name) {
+                columns.push(name);
+            }
+        }
+    }
+
+    let transposed = columns
+        .into_iter()
+        .map(|column| {
+            let mut transposed_row = DataDict::default();
+            transposed_row.insert("column", Value::string(column));
+            for (i, row) in rows.iter().enumerate() {
+                let value = row.get(column).cloned().unwrap_or_else(Value::none);
+                transposed_row.insert(format!("row{}", i), value);
+            }
+            Value::object(transposed_row)
+        })
+        .collect::<Vec<_>>();
+    Value::list(transposed)
+}
+
+// transpose --header-row : the first cell of every row names a column,
+// so transpose | transpose --header-row round trips
+fn columns_to_rows(rows: &[&DataDict]) -> Result<Value, ShellError> {
+    let header = match rows.first().and_then(|row| row.iter().next()) {
+        Some((header, _)) => header,
+        None => return Ok(Value::list(vec![])),
+    };
+
+    let cells: Vec<&str> = rows[0]
+        .iter()
+        .skip(1)
+        .map(|(name, _)| name.as_str())
+        .collect();
+
+    let mut records = vec![];
+    for cell in &cells {
+        let mut record = DataDict::default();
+        for row in rows {
+            let name = match row.get(header) {
+                Some(name) => name.to_string(),
+                None => {
+                    return Err(ShellError::new(format!(
+                        "transpose : no column named {}",
+                        header
+                    )))
+                }
+            };
+            let value = row.get(cell).cloned().unwrap_or_else(Value::none);
+            record.insert(name, value);
+        }
+        records.push(Value::object(record));
+    }
+
+    // a transposed record comes back as a record
+    match (cells.as_slice(), records.len()) {
+        (["value"], 1) => Ok(records.remove(0)),
+        _ => Ok(Value::list(records)),
+    }
+}
@@ -0,0 +1,518 @@
+use std::{
+    env, fs,
+    io::{ErrorKind, Write},
+    os::unix::fs::{DirBuilderExt, MetadataExt},
+    path::{Path, PathBuf},
+};
+
+use chrono::{Local, NaiveDateTime, TimeZone};
+
+use crate::{
+    error::ShellError,
+    paths::expand_path,
+    stream::RushStream,
+    types::{datadict::DataDict, primary::Value},
+};
+
+use super::{
+    fileops::{change_row, move_path},
+    strings::as_text,
+    Args, Command,
+};
+
+const DATE_FORMAT: &str = "%Y-%m-%dT%H:%M:%S";
+
+#[derive(Debug)]
+pub struct Trash;
+
+impl Command for Trash {
+    fn run(&self, args: Args) -> Result<Value, ShellError> {
+        let mut params = args.args.iter().map(|arg| arg.to_string());
+        match params.next().as_deref() {
+            Some("list") => Ok(Value::list(
+                list_trash()?
+                    .iter()
+                    .map(TrashEntry::to_value)
+                    .collect::<Vec<_>>(),
+            )),
+            Some("restore") => restore(params.collect(), args.instream),
+            _ => Err(ShellError::new(
+                "trash expects a subcommand, e.g. trash list or trash restore",
+            )),
+        }
+    }
+}
+
+/// A trashed file, `id` is its name inside the `trash` directory
+struct TrashEntry {
+    id: String,
+    trash: PathBuf,
+    original: PathBuf,
+    deleted: Option<NaiveDateTime>,
+}
+
+/// A trash directory, those of other mounts record paths relative to their `top`
+struct TrashDir {
+    path: PathBuf,
+    top: Option<PathBuf>,
+}
+
+impl TrashEntry {
+    fn to_value(&self) -> Value {
+        let mut dict = DataDict::default();
+        dict.insert("id", Value::string(&self.id));
+        dict.insert("path", Value::path(&self.original));
+        let deleted = self
+            .deleted
+            .and_then(|date| Local.from_local_datetime(&date).single())
+            .map(Value::date)
+            .unwrap_or_else(Value::none);
+        dict.insert("deleted", deleted);
+        Value::object(dict)
+    }
+}
+
+// the home trash of the freedesktop.org trash spec
+fn home_trash() -> Result<PathBuf, ShellError> {
+    let data_home = match env::var_os("XDG_DATA_HOME") {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => home::home_dir()
+            .ok_or_else(|| ShellError::new("trash : could not find home path"))?
+            .join(".local/share"),
+    };
+    Ok(data_home.join("Trash"))
+}
+
+fn user_id() -> u32 {
+    // getuid cannot fail
+    unsafe { libc::getuid() }
+}
+
+// files on other filesystems go to $topdir/.Trash-$uid, so trashing never copies them
+fn trash_for(original: &Path) -> Result<TrashDir, ShellError> {
+    let home = home_trash()?;
+    fs::create_dir_all(&home)?;
+
+    let device = |path: &Path| fs::metadata(path).map(|metadata| metadata.dev());
+    let parent = original.parent().unwrap_or_else(|| Path::new("/"));
+    let file_device = device(parent)?;
+    if device(&home)? == file_device {
+        return Ok(TrashDir {
+            path: home,
+            top: None,
+        });
+    }
+
+    // the mount point is the last ancestor on the same device
+    let mut top = parent;
+    while let Some(up) = top.parent() {
+        if device(up)? != file_device {
+            break;
+        }
+        top = up;
+    }
+
+    let path = top.join(format!(".Trash-{}", user_id()));
+    let unusable = |reason: String| {
+        ShellError::new(format!(
+            "trash : cannot use {} : {}, use rm --permanent",
+            path.display(),
+            reason
+        ))
+    };
+    fs::DirBuilder::new()
+        .recursive(true)
+        .mode(0o700)
+        .create(&path)
+        .map_err(|err| unusable(err.to_string()))?;
+    let metadata = fs::symlink_metadata(&path)?;
+    if !metadata.is_dir() || metadata.uid() != user_id() {
+        return Err(unusable("not a directory owned by you".to_string()));
+    }
+
+    Ok(TrashDir {
+        path,
+        top: Some(top.to_path_buf()),
+    })
+}
+
+// the home trash and the per mount trashes that exist
+fn trash_dirs() -> Result<Vec<TrashDir>, ShellError> {
+    let mut dirs = vec![TrashDir {
+        path: home_trash()?,
+        top: None,
+    }];
+
+    let mounts = fs::read_to_string("/proc/self/mounts").unwrap_or_default();
+    for line in mounts.lines() {
+        let top = match line.split_whitespace().nth(1) {
+            Some(top) => PathBuf::from(unescape_mount(top)),
+            None => continue,
+        };
+        let path = top.join(format!(".Trash-{}", user_id()));
+        if path.join("info").is_dir() && !dirs.iter().any(|dir| dir.path == path) {
+            dirs.push(TrashDir {
+                path,
+                top: Some(top),
+            });
+        }
+    }
+    Ok(dirs)
+}
+
+// mount points escape whitespace and backslashes as octal
+fn unescape_mount(path: &str) -> String {
+    path.replace("\\040", " ")
+        .replace("\\011", "\t")
+        .replace("\\012", "\n")
+        .replace("\\134", "\\")
+}
+
+/// Moves a file or directory into the trash, recording where it came from
+pub fn move_to_trash(path: &Path) -> Result<(), ShellError> {
+    let original = absolute_path(path)?;
+    let trash = trash_for(&original)?;
+    let (files, info) = (trash.path.join("files"), trash.path.join("info"));
+    fs::create_dir_all(&files)?;
+    fs::create_dir_all(&info)?;
+
+    let file_name = original
+        .file_name()
+        .ok_or_else(|| ShellError::new("cannot trash this path"))?
+        .to_string_lossy()
+        .into_owned();
+
+    // creating the info file first reserves the name against other trashers
+    let mut attempt = 1;
+    let (id, mut info_file) = loop {
+        let id = match attempt {
+            1 => file_name.clone(),
+            n => format!("{}.{}", file_name, n),
+        };
+        let info_path = info.join(format!("{}.trashinfo", id));
+        match fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&info_path)
+        {
+            Ok(file) if !files.join(&id).exists() => break (id, file),
+            Ok(_) => fs::remove_file(&info_path)?,
+            Err(err) if err.kind() == ErrorKind::AlreadyExists => {}
+            Err(err) => return Err(err.into()),
+        }
+        attempt += 1;
+    };
+
+    let recorded = match &trash.top {
+        Some(top) => original.strip_prefix(top).unwrap_or(&original),
+        None => &original,
+    };
+    writeln!(
+        info_file,
+        "[Trash Info]\nPath={}\nDeletionDate={}",
+        percent_encode(&recorded.to_string_lossy()),
+        Local::now().format(DATE_FORMAT)
+    )?;
+
+    let info_path = info.join(format!("{}.trashinfo", id));
+    if let Err(err) = move_path(&original, &files.join(&id)) {
+        let _ = fs::remove_file(info_path);
+        return Err(err);
+    }
+    Ok(())
+}
+
+// the parent is resolved, but not the file itself, so symlinks are trashed as links
+fn absolute_path(path: &Path) -> Result<PathBuf, ShellError> {
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| ShellError::new("cannot trash this path"))?;
+    let parent = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    Ok(parent.canonicalize()?.join(file_name))
+}
+
+fn list_trash() -> Result<Vec<TrashEntry>, ShellError> {
+    let mut trashed = vec![];
+    for trash in trash_dirs()? {
+        read_trash(&trash, &mut trashed)?;
+    }
+    trashed.sort_by(|a, b| (a.deleted, &a.id).cmp(&(b.deleted, &b.id)));
+    Ok(trashed)
+}
+
+fn read_trash(trash: &TrashDir, trashed: &mut Vec<TrashEntry>) -> Result<(), ShellError> {
+    let entries = match fs::read_dir(trash.path.join("info")) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(()),
+        Err(err) => return Err(err.into()),
+    };
+
+    for entry in entries {
+        let entry = entry?;
+        let file_name = entry.file_name().to_string_lossy().into_owned();
+        let id = match file_name.strip_suffix(".trashinfo") {
+            Some(id) => id.to_string(),
+            None => continue,
+        };
+
+        let contents = fs::read_to_string(entry.path())?;
+        let field = |key: &str| {
+            contents
+                .lines()
+                .find_map(|line| line.strip_prefix(key)?.strip_prefix('='))
+        };
+        let original = match (field("Path"), &trash.top) {
+            (Some(path), Some(top)) => top.join(percent_decode(path)),
+            (Some(path), None) => PathBuf::from(percent_decode(path)),
+            (None, _) => continue,
+        };
+        let deleted = field("DeletionDate")
+            .and_then(|date| NaiveDateTime::parse_from_str(date, DATE_FORMAT).ok());
+
+        trashed.push(TrashEntry {
+            id,
+            trash: trash.path.clone(),
+            original,
+            deleted,
+        });
+    }
+    Ok(())
+}
+
+// trash restore <id or original path>..., or piped trash list rows
+fn restore(targets: Vec<String>, instream: RushStream) -> Result<Value, ShellError> {
+    let mut targets = targets;
+    let piped = match instream {
+        RushStream::Internal(Value::List(rows)) => rows,
+        RushStream::Internal(row) => vec![row],
+        RushStream::External(_) => {
+            return Err(ShellError::new("external streams not supported yet"))
+        }
+        RushStream::None => vec![],
+    };
+    for row in &piped {
+        let id = match row {
+            Value::Object(dict) => dict.get("id").and_then(as_text),
+            other => as_text(other),
+        };
+        targets.push(id.ok_or_else(|| ShellError::new("trash restore expects rows with an id"))?);
+    }
+
+    if targets.is_empty() {
+        return Err(ShellError::new(
+            "trash restore expects an id or path, see trash list",
+        ));
+    }
+
+    let mut trashed = list_trash()?;
+    let mut changes = vec![];
+
+    for target in targets {
+        // by id, or the latest trashed copy of an original path
+        let position = trashed
+            .iter()
+            .position(|entry| entry.id == target)
+            .or_else(|| {
+                let expanded = expand_path(&target);
+                let wanted = absolute_path(&expanded).unwrap_or(expanded);
+                trashed.iter().rposition(|entry| entry.original == wanted)
+            });
+
+        let entry = match position {
+            Some(position) => trashed.remove(position),
+            None => {
+                let result = Err(ShellError::new("not in the trash"));
+                changes.push(change_row(
+                    "restored",
+                    Path::new(&target),
+                    None,
+                    result,
+                    false,
+                ));
+                continue;
+            }
+        };
+
+        let source = entry.trash.join("files").join(&entry.id);
+        let result = restore_entry(&entry, &source);
+        changes.push(change_row(
+            "restored",
+            &source,
+            Some(&entry.original),
+            result,
+            false,
+        ));
+    }
+
+    Ok(Value::list(changes))
+}
+
+fn restore_entry(entry: &TrashEntry, source: &Path) -> Result<(), ShellError> {
+    if fs::symlink_metadata(&entry.original).is_ok() {
+        return Err(ShellError::new(
+            "a file already exists at the original path",
+        ));
+    }
+    if let Some(parent) = entry.original.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    move_path(source, &entry.original)?;
+    let info = entry.trash.join("info");
+    fs::remove_file(info.join(format!("{}.trashinfo", entry.id)))?;
+    Ok(())
+}
+
+// paths in .trashinfo files are url escaped
+fn percent_encode(path: &str) -> String {
+    let mut encoded = String::new();
+    for byte in path.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b'/' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = vec![];
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = match bytes[i] {
+            b'%' => text
+                .get(i + 1..i + 3)
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok()),
+            _ => None,
+        };
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+
+    // the trash is found through XDG_DATA_HOME, which is shared by the whole process
+    static DATA_HOME: Mutex<()> = Mutex::new(());
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("rush-trash-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("data")).unwrap();
+        env::set_var("XDG_DATA_HOME", dir.join("data"));
+        dir
+    }
+
+    // other mounts may have trashes of their own
+    fn trashed(dir: &Path) -> Vec<TrashEntry> {
+        let mut trashed = list_trash().unwrap();
+        trashed.retain(|entry| entry.trash.starts_with(dir));
+        trashed
+    }
+
+    fn statuses(changes: Value) -> Vec<String> {
+        match changes {
+            Value::List(rows) => rows
+                .iter()
+                .map(|row| row.get_field("status").unwrap().to_string())
+                .collect(),
+            other => panic!("expected rows, found {:?}", other),
+        }
+    }
+
+    #[test]
+    fn percent_encoding_round_trips() {
+        for path in [
+            "/tmp/plain.txt",
+            "/tmp/with space/100%.txt",
+            "/tmp/ünïcode#?.md",
+        ] {
+            assert_eq!(percent_decode(&percent_encode(path)), path);
+        }
+        assert_eq!(percent_encode("/a b%"), "/a%20b%25");
+        // a stray % is kept as it is
+        assert_eq!(percent_decode("/a%2"), "/a%2");
+    }
+
+    #[test]
+    fn trash_and_restore() {
+        let _guard = DATA_HOME.lock().unwrap();
+        let dir = scratch_dir("restore");
+        let original = dir.join("with space").join("100%.txt");
+        fs::create_dir_all(original.parent().unwrap()).unwrap();
+        fs::write(&original, "contents").unwrap();
+
+        move_to_trash(&original).unwrap();
+        assert!(!original.exists());
+        let info = fs::read_to_string(dir.join("data/Trash/info/100%.txt.trashinfo")).unwrap();
+        assert!(info.contains("/with%20space/100%25.txt"));
+
+        let entries = trashed(&dir);
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].original, original);
+
+        let changes = restore(vec![entries[0].id.clone()], RushStream::None).unwrap();
+        assert_eq!(statuses(changes), ["ok"]);
+        assert_eq!(fs::read_to_string(&original).unwrap(), "contents");
+        assert!(trashed(&dir).is_empty());
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn same_names_get_their_own_ids() {
+        let _guard = DATA_HOME.lock().unwrap();
+        let dir = scratch_dir("names");
+        for sub in ["a", "b"] {
+            let path = dir.join(sub).join("notes.txt");
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(&path, sub).unwrap();
+            move_to_trash(&path).unwrap();
+        }
+
+        let mut ids: Vec<String> = trashed(&dir).into_iter().map(|e| e.id).collect();
+        ids.sort();
+        assert_eq!(ids, ["notes.txt", "notes.txt.2"]);
+        let files = dir.join("data/Trash/files");
+        assert_eq!(fs::read_to_string(files.join("notes.txt")).unwrap(), "a");
+        assert_eq!(fs::read_to_string(files.join("notes.txt.2")).unwrap(), "b");
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn restore_never_overwrites() {
+        let _guard = DATA_HOME.lock().unwrap();
+        let dir = scratch_dir("conflict");
+        let original = dir.join("kept.txt");
+        fs::write(&original, "old").unwrap();
+        move_to_trash(&original).unwrap();
+        fs::write(&original, "new").unwrap();
+
+        let target = original.to_string_lossy().into_owned();
+        let changes = restore(vec![target], RushStream::None).unwrap();
+        assert_eq!(statuses(changes), ["failed"]);
+        assert_eq!(fs::read_to_string(&original).unwrap(), "new");
+        assert_eq!(trashed(&dir).len(), 1);
+
+        let changes = restore(vec!["missing".to_string()], RushStream::None).unwrap();
+        assert_eq!(statuses(changes), ["failed"]);
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
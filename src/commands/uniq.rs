@@ -0,0 +1,51 @@
+use std::collections::BTreeSet;
+
+use crate::{error::ShellError, types::primary::Value};
+
+use super::{list_input, Args, Command};
+
+#[derive(Debug)]
+pub struct Uniq;
+
+impl Command for Uniq {
+    fn run(&self, args: Args) -> Result<Value, ShellError> {
+        let items = list_input("uniq", args.instream)?;
+        dedup_by(items, |item| Ok(item.clone()))
+    }
+}
+
+#[derive(Debug)]
+pub struct UniqBy;
+
+impl Command for UniqBy {
+    fn run(&self, args: Args) -> Result<Value, ShellError> {
+        let column = match args.args.first() {
+            Some(column) => column.to_string(),
+            None => {
+                return Err(ShellError::new(
+                    "uniq-by expects a column, e.g. uniq-by name",
+                ))
+            }
+        };
+
+        let rows = list_input("uniq-by", args.instream)?;
+        dedup_by(rows, |row| row.get_field(&column))
+    }
+}
+
+// Keeps the first item for every key, in the original order
+fn dedup_by(
+    items: Vec<Value>,
+    key: impl Fn(&Value) -> Result<Value, ShellError>,
+) -> Result<Value, ShellError> {
+    let mut seen = BTreeSet::new();
+    let mut unique = vec![];
+
+    for item in items {
+        if seen.insert(key(&item)?) {
+            unique.push(item);
+        }
+    }
+
+    Ok(Value::list(unique))
+}
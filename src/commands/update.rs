@@ -0,0 +1,28 @@
+use crate::{error::ShellError, types::primary::Value};
+
+use super::{insert::cell_value, map_records, Args, Command};
+
+#[derive(Debug)]
+pub struct Update;
+
+impl Command for Update {
+    fn run(&self, args: Args) -> Result<Value, ShellError> {
+        let (column, new_value) = match args.args.as_slice() {
+            [column, new_value] => (column.to_string(), new_value),
+            _ => {
+                return Err(ShellError::new(
+                    "update expects a column and a value, e.g. update size f -> f.size * 2",
+                ))
+            }
+        };
+
+        map_records("update", args.instream, |mut row| {
+            if row.get(&column).is_none() {
+                return Err(ShellError::new(format!("no column named {}", column)));
+            }
+            let value = cell_value(new_value, &row, args.ctx)?;
+            row.insert(&column, value);
+            Ok(row)
+        })
+    }
+}
@@ -0,0 +1,107 @@
+use std::{fs, path::PathBuf};
+
+use crate::{
+    error::ShellError,
+    paths::expand_path,
+    stream::RushStream,
+    types::{datadict::DataDict, primary::Value},
+};
+
+use super::{
+    fileops::{expand_paths, FileArgs},
+    strings::as_text,
+    Args, Command,
+};
+
+const COUNTS: [(&str, char); 4] = [
+    ("lines", 'l'),
+    ("words", 'w'),
+    ("bytes", 'c'),
+    ("chars", 'm'),
+];
+
+#[derive(Debug)]
+pub struct Wc;
+
+impl Command for Wc {
+    fn run(&self, args: Args) -> Result<Value, ShellError> {
+        let file_args = FileArgs::parse("wc", &args.args, &COUNTS)?;
+
+        let mut records = vec![];
+        if !file_args.paths.is_empty() {
+            for file in expand_paths("wc", &file_args.paths)? {
+                records.push(count_file(file)?);
+            }
+        } else {
+            let items = match args.instream {
+                RushStream::Internal(Value::List(items)) => items,
+                RushStream::Internal(value) => vec![value],
+                RushStream::External(_) => {
+                    return Err(ShellError::new("external streams not supported yet"))
+                }
+                RushStream::None => return Err(ShellError::new("wc expects a file or an input")),
+            };
+
+            // ls rows are counted by file, skipping directories, strings by their own text
+            for item in items {
+                let record = match &item {
+                    Value::Object(dict) => match dict.get("name").and_then(as_text) {
+                        Some(name) if expand_path(&name).is_dir() => continue,
+                        Some(name) => count_file(expand_path(&name))?,
+                        None => return Err(ShellError::new("wc expects rows with a name column")),
+                    },
+                    other => match as_text(other) {
+                        Some(text) => count(&text, None),
+                        None => {
+                            return Err(ShellError::new(format!(
+                                "wc expects a string, found {}",
+                                other.type_name()
+                            )))
+                        }
+                    },
+                };
+                records.push(record);
+            }
+        }
+
+        // wc -l shows only the line count, without flags every count is shown
+        if COUNTS.iter().any(|(name, _)| file_args.has(name)) {
+            for record in &mut records {
+                for (name, _) in COUNTS {
+                    if !file_args.has(name) {
+                        record.remove(name);
+                    }
+                }
+            }
+        }
+        let mut records: Vec<Value> = records.into_iter().map(Value::object).collect();
+
+        // a single input is a record, several are a table
+        match records.len() {
+            1 => Ok(records.remove(0)),
+            _ => Ok(Value::list(records)),
+        }
+    }
+}
+
+fn count_file(path: PathBuf) -> Result<DataDict, ShellError> {
+    let bytes = fs::read(&path)
+        .map_err(|err| ShellError::new(format!("wc : {} : {}", path.display(), err)))?;
+    let text = String::from_utf8_lossy(&bytes);
+    let mut dict = count(&text, Some(path));
+    // bytes on disk, not of the lossily decoded text
+    dict.insert("bytes", Value::size(bytes.len() as u64));
+    Ok(dict)
+}
+
+fn count(text: &str, path: Option<PathBuf>) -> DataDict {
+    let mut dict = DataDict::default();
+    if let Some(path) = path {
+        dict.insert("file", Value::path(path));
+    }
+    dict.insert("lines", Value::int(text.lines().count() as i64));
+    dict.insert("words", Value::int(text.split_whitespace().count() as i64));
+    dict.insert("bytes", Value::size(text.len() as u64));
+    dict.insert("chars", Value::int(text.chars().count() as i64));
+    dict
+}
@@ -0,0 +1,120 @@
+use std::{
+    io::{self, Write},
+    path::Path,
+};
+
+use crate::{
+    environment::Environment,
+    error::ShellError,
+    frecency::{record_visit, Frecency, Visit},
+    types::{datadict::DataDict, primary::Value},
+};
+
+use super::{Args, Command};
+
+// zi offers the best few matches
+const CHOICES: usize = 20;
+
+#[derive(Debug)]
+pub struct Z;
+
+#[derive(Debug)]
+pub struct Zi;
+
+impl Command for Z {
+    fn run(&self, args: Args) -> Result<Value, ShellError> {
+        let mut list = false;
+        let mut fragments = vec![];
+        for arg in &args.args {
+            match arg.to_string().as_str() {
+                "--list" | "-l" => list = true,
+                fragment => fragments.push(fragment.to_string()),
+            }
+        }
+
+        let matches = Frecency::load().matches(&fragments);
+
+        // z --list shows every match with its score
+        if list {
+            let rows = matches
+                .into_iter()
+                .map(|(visit, score)| {
+                    let mut dict = DataDict::default();
+                    dict.insert("path", Value::path(visit.path));
+                    dict.insert("score", Value::float(score));
+                    dict.insert("rank", Value::float(visit.rank));
+                    let visited =
+                        std::time::UNIX_EPOCH + std::time::Duration::from_secs(visit.last_visit);
+                    dict.insert("visited", Value::time(visited));
+                    Value::object(dict)
+                })
+                .collect::<Vec<_>>();
+            return Ok(Value::list(rows));
+        }
+
+        if fragments.is_empty() {
+            return Err(ShellError::new(
+                "z expects part of a directory, e.g. z proj",
+            ));
+        }
+
+        let best = match matches.into_iter().next() {
+            Some((visit, _)) => visit.path,
+            None => {
+                return Err(ShellError::new(format!(
+                    "z : no visited directory matches {}",
+                    fragments.join(" ")
+                )))
+            }
+        };
+
+        jump("z", &args.env, &best)
+    }
+}
+
+impl Command for Zi {
+    fn run(&self, args: Args) -> Result<Value, ShellError> {
+        let fragments: Vec<String> = args.args.iter().map(|arg| arg.to_string()).collect();
+        let matches = Frecency::load().matches(&fragments);
+
+        let choices: Vec<_> = matches.into_iter().take(CHOICES).collect();
+        let chosen = match choices.len() {
+            0 => {
+                return Err(ShellError::new(format!(
+                    "zi : no visited directory matches {}",
+                    fragments.join(" ")
+                )))
+            }
+            1 => 0,
+            _ => choose(&choices)?,
+        };
+
+        let path = choices[chosen].0.path.clone();
+        jump("zi", &args.env, &path)
+    }
+}
+
+// numbered matches go to stderr, the answer is read from stdin
+fn choose(choices: &[(Visit, f64)]) -> Result<usize, ShellError> {
+    let mut stderr = io::stderr();
+    for (index, (visit, _)) in choices.iter().enumerate() {
+        writeln!(stderr, "{:>3}  {}", index + 1, visit.path.display())?;
+    }
+    write!(stderr, "zi > ")?;
+    stderr.flush()?;
+
+    let mut answer = String::new();
+    io::stdin().read_line(&mut answer)?;
+    match answer.trim().parse::<usize>() {
+        Ok(number) if (1..=choices.len()).contains(&number) => Ok(number - 1),
+        _ => Err(ShellError::new("zi : no directory chosen")),
+    }
+}
+
+fn jump(command: &str, env: &Environment, path: &Path) -> Result<Value, ShellError> {
+    env.set_cwd(&path.to_path_buf())
+        .map_err(|err| ShellError::new(format!("{} : {} : {}", command, path.display(), err)))?;
+    record_visit(path);
+
+    Ok(Value::string(path.to_string_lossy()))
+}
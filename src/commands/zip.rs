@@ -0,0 +1,40 @@
+use crate::{
+    error::ShellError,
+    types::{datadict::DataDict, primary::Value},
+};
+
+use super::{list_input, Args, Command};
+
+#[derive(Debug)]
+pub struct Zip;
+
+impl Command for Zip {
+    fn run(&self, args: Args) -> Result<Value, ShellError> {
+        let others = match args
+            .args
+            .into_iter()
+            .next()
+            .map(Value::expand_range)
+            .transpose()?
+        {
+            Some(Value::List(others)) => others,
+            _ => return Err(ShellError::new("zip expects a list, e.g. zip [1, 2, 3]")),
+        };
+
+        let items = list_input("zip", args.instream)?;
+
+        // pairs stop at the shorter list
+        let pairs = items
+            .into_iter()
+            .zip(others)
+            .map(|(left, right)| {
+                let mut pair = DataDict::default();
+                pair.insert("left", left);
+                pair.insert("right", right);
+                Value::object(pair)
+            })
+            .collect::<Vec<_>>();
+
+        Ok(Value::list(pairs))
+    }
+}
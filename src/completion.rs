@@ -3,7 +3,11 @@ use std::path;
 use colored::Colorize;
 use rustyline::completion::{self, Completer, Pair};
 
-use crate::parselex::{self, lex::Token};
+use crate::{
+    frecency::Frecency,
+    parselex::{self, lex::Token},
+    paths::expand_path,
+};
 
 // Highlight all candidates that start with the same prefix
 fn highlight_candidates(candidates: &mut [Pair], prefix: &str) {
@@ -28,6 +32,85 @@ fn current_token(line: &str, pos: usize) -> Token {
         .unwrap_or(Token::None)
 }
 
+/// A word with environment variables, completed as the path it expands to
+struct ExpandedWord {
+    start: usize,
+    line: String,
+}
+
+impl ExpandedWord {
+    // candidates replace the whole typed word with the expanded path
+    fn complete(
+        &self,
+        completer: &completion::FilenameCompleter,
+    ) -> rustyline::Result<(usize, Vec<Pair>)> {
+        let (complete_pos, candidates) = completer.complete_path(&self.line, self.line.len())?;
+        let typed = &self.line[self.start..complete_pos.max(self.start)];
+        let candidates = candidates
+            .into_iter()
+            .map(|candidate| Pair {
+                display: candidate.display,
+                replacement: format!("{}{}", typed, candidate.replacement),
+            })
+            .collect();
+        Ok((self.start, candidates))
+    }
+}
+
+// $HOME/Doc completes like ~/Doc, only when the cursor ends the word
+fn expanded_word(line: &str, pos: usize) -> Option<ExpandedWord> {
+    let (word, span) = parselex::lex(line)
+        .into_iter()
+        .find(|(_token, span)| span.start <= pos && span.end >= pos)
+        .and_then(|(token, span)| match token {
+            Token::Item(word) if word.contains('$') && span.end == pos => Some((word, span)),
+            _ => None,
+        })?;
+
+    let expanded = expand_path(&word).to_string_lossy().into_owned();
+    Some(ExpandedWord {
+        start: span.start,
+        line: format!("{}{}", &line[..span.start], expanded),
+    })
+}
+
+/// Frequently visited directories, offered to cd when no file matches
+#[derive(Default)]
+pub struct FrecentDirCompleter;
+
+impl FrecentDirCompleter {
+    // only the word right after cd, e.g. cd proj<tab>
+    fn complete(&self, line: &str, pos: usize) -> Option<(usize, Vec<Pair>)> {
+        let words: Vec<_> = parselex::lex(&line[..pos])
+            .into_iter()
+            .filter(|(token, _span)| !matches!(token, Token::Whitespace))
+            .collect();
+
+        let (word, start) = match words.as_slice() {
+            [(Token::Item(command), _), (Token::Item(word), span)]
+                if command == "cd" && span.end == pos =>
+            {
+                (word.clone(), span.start)
+            }
+            _ => return None,
+        };
+
+        let candidates: Vec<Pair> = Frecency::load()
+            .matches(&[word])
+            .into_iter()
+            .take(10)
+            .map(|(visit, _score)| {
+                let path = visit.path.to_string_lossy().into_owned();
+                Pair {
+                    display: path.clone(),
+                    replacement: path,
+                }
+            })
+            .collect();
+        Some((start, candidates))
+    }
+}
+
 #[derive(Default)]
 pub struct FilenameCompleter {
     completer: completion::FilenameCompleter,
@@ -37,6 +120,7 @@ pub struct FilenameCompleter {
 #[derive(Default)]
 pub struct RushCompleter {
     pub file_completer: FilenameCompleter,
+    pub dir_completer: FrecentDirCompleter,
 }
 
 impl Completer for RushCompleter {
@@ -48,7 +132,15 @@ impl Completer for RushCompleter {
         pos: usize,
         ctx: &rustyline::Context<'_>,
     ) -> rustyline::Result<(usize, Vec<Self::Candidate>)> {
-        self.file_completer.complete(line, pos, ctx)
+        let (complete_pos, candidates) = self.file_completer.complete(line, pos, ctx)?;
+        if !candidates.is_empty() {
+            return Ok((complete_pos, candidates));
+        }
+
+        match self.dir_completer.complete(line, pos) {
+            Some(frecent) => Ok(frecent),
+            None => Ok((complete_pos, candidates)),
+        }
     }
 }
 
@@ -60,7 +152,10 @@ impl Completer for FilenameCompleter {
         pos: usize,
         _ctx: &rustyline::Context<'_>,
     ) -> rustyline::Result<(usize, Vec<Self::Candidate>)> {
-        let (complete_pos, candidates) = self.completer.complete_path(line, pos)?;
+        let (complete_pos, candidates) = match expanded_word(line, pos) {
+            Some(expanded) => expanded.complete(&self.completer)?,
+            None => self.completer.complete_path(line, pos)?,
+        };
 
         // remove dotfiles
         let mut candidates: Vec<Pair> = candidates
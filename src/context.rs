@@ -1,6 +1,6 @@
 use std::{collections::BTreeMap, path::Path, rc::Rc};
 
-use crate::{commands::Command, environment::Environment, error::ShellError};
+use crate::{commands::Command, environment::Environment, error::ShellError, paths::resolve_path};
 
 #[derive(Default, Clone)]
 pub struct Context {
@@ -46,4 +46,18 @@ impl Context {
     pub fn command_exists(&self, name: &str) -> bool {
         self.valid_commands.contains_key(name) || self.external_commands.get(name).is_some()
     }
+
+    /// Commands run as a process, either found on PATH or given as a path, e.g. ./build.sh
+    pub fn is_external(&self, name: &str) -> bool {
+        !self.valid_commands.contains_key(name)
+            && (self.external_commands.get(name).is_some() || name.contains('/'))
+    }
+
+    /// A directory typed where a command goes is run as `cd`, commands of the same name win.
+    /// Only explicit paths count, so a stray word never jumps through CDPATH
+    pub fn is_directory_command(&self, name: &str) -> bool {
+        let explicit =
+            matches!(name, "." | "..") || name.starts_with(['/', '~']) || name.contains('/');
+        explicit && !self.command_exists(name) && resolve_path(&self.env.cwd(), name).is_dir()
+    }
 }
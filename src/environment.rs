@@ -1,14 +1,43 @@
-use std::{env, path::PathBuf};
+use std::{cell::RefCell, env, path::PathBuf};
 
 #[derive(Debug, Default)]
-pub struct Environment {}
+pub struct Environment {
+    previous_dir: RefCell<Option<PathBuf>>,
+    dir_stack: RefCell<Vec<PathBuf>>,
+}
 
 impl Environment {
     pub fn cwd(&self) -> PathBuf {
         env::current_dir().unwrap()
     }
 
+    /// Changes directory, remembering the old one for cd - and exporting PWD and OLDPWD
     pub fn set_cwd(&self, dir_path: &PathBuf) -> Result<(), std::io::Error> {
-        env::set_current_dir(dir_path)
+        let old_dir = env::current_dir().ok();
+        env::set_current_dir(dir_path)?;
+
+        if let Some(old_dir) = old_dir {
+            env::set_var("OLDPWD", &old_dir);
+            *self.previous_dir.borrow_mut() = Some(old_dir);
+        }
+        env::set_var("PWD", self.cwd());
+        Ok(())
+    }
+
+    pub fn previous_dir(&self) -> Option<PathBuf> {
+        self.previous_dir.borrow().clone()
+    }
+
+    pub fn push_dir(&self, dir_path: PathBuf) {
+        self.dir_stack.borrow_mut().push(dir_path);
+    }
+
+    pub fn pop_dir(&self) -> Option<PathBuf> {
+        self.dir_stack.borrow_mut().pop()
+    }
+
+    /// Pushed directories, most recent first
+    pub fn dir_stack(&self) -> Vec<PathBuf> {
+        self.dir_stack.borrow().iter().rev().cloned().collect()
     }
 }
@@ -0,0 +1,618 @@
+use std::{
+    borrow::Cow,
+    cmp::Ordering,
+    collections::HashMap,
+    fmt,
+    io::Read,
+    process::Stdio,
+    time::{Duration, SystemTime},
+};
+
+use chrono::Local;
+use regex::Regex;
+
+use crate::{
+    commands::{CommandType, ExternalCommand, InternalCommand},
+    context::Context,
+    error::ShellError,
+    parselex::{
+        lex::Operator,
+        parser::{Expr, ParsedCommand, ParsedPipeline, Val},
+    },
+    stream::RushStream,
+    types::{
+        datadict::DataDict,
+        primary::{Primitive, Value},
+    },
+};
+
+/// Variables bound by lambda parameters
+#[derive(Debug, Clone, Default)]
+pub struct Scope {
+    vars: HashMap<String, Value>,
+}
+
+impl Scope {
+    pub fn with(&self, name: impl Into<String>, value: Value) -> Self {
+        let mut scope = self.clone();
+        scope.vars.insert(name.into(), value);
+        scope
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        self.vars.get(name)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Lambda {
+    pub param: String,
+    pub body: Expr,
+    pub scope: Scope,
+}
+
+impl Lambda {
+    pub fn call(&self, ctx: &Context, input: Value) -> Result<Value, ShellError> {
+        let scope = self.scope.with(&self.param, input);
+        eval_expr(ctx, &self.body, &scope)
+    }
+}
+
+impl fmt::Display for Lambda {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} -> {}", self.param, self.body)
+    }
+}
+
+impl PartialEq for Lambda {
+    // Not used, needed to satisfy compiler
+    fn eq(&self, _other: &Self) -> bool {
+        false
+    }
+}
+
+impl Eq for Lambda {}
+
+impl PartialOrd for Lambda {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Lambda {
+    // Not used, needed to satisfy compiler
+    fn cmp(&self, _other: &Self) -> Ordering {
+        Ordering::Equal
+    }
+}
+
+pub fn run_pipeline(
+    ctx: &Context,
+    parsed_pipeline: &ParsedPipeline,
+    scope: &Scope,
+) -> Result<Value, ShellError> {
+    let command_list = build_pipeline(ctx, parsed_pipeline, scope)?;
+
+    let mut pipeline_iter = command_list.into_iter().peekable();
+
+    let mut stream = RushStream::None;
+
+    loop {
+        let (curr, next) = (pipeline_iter.next(), pipeline_iter.peek());
+
+        stream = match (curr, next) {
+            (Some(final_command), None) => match final_command {
+                CommandType::Internal(internal) => {
+                    return internal.run(ctx, stream);
+                }
+                CommandType::External(external) => {
+                    let result = external.run(stream, Stdio::inherit());
+                    let mut result = match result {
+                        Ok(child) => child,
+                        Err(_) => return Err(ShellError::new("rush : command not found")),
+                    };
+                    result.wait()?;
+                    return Ok(Value::none());
+                }
+                CommandType::Literal(value) => return Ok(value),
+            },
+            (Some(curr_command), Some(_)) => match curr_command {
+                CommandType::Internal(internal) => {
+                    let result = internal.run(ctx, stream)?;
+                    RushStream::Internal(result)
+                }
+                CommandType::External(external) => {
+                    let mut child = external.run(stream, Stdio::piped())?;
+                    let mut stdout = child.stdout.take().unwrap();
+                    match next {
+                        Some(CommandType::External(_)) => RushStream::External(stdout.into()),
+                        // internal commands get the collected output as a string
+                        _ => {
+                            let mut output = vec![];
+                            stdout.read_to_end(&mut output)?;
+                            child.wait()?;
+                            let output = String::from_utf8_lossy(&output);
+                            RushStream::Internal(Value::string(output.trim_end_matches('\n')))
+                        }
+                    }
+                }
+                CommandType::Literal(value) => RushStream::Internal(value),
+            },
+            (_, _) => return Err(ShellError::new("Not yet implemented")),
+        }
+    }
+}
+
+fn build_pipeline(
+    ctx: &Context,
+    parsed_pipeline: &ParsedPipeline,
+    scope: &Scope,
+) -> Result<Vec<CommandType>, ShellError> {
+    let commands = &parsed_pipeline.commands;
+
+    commands
+        .iter()
+        .map(|command| parsed_to_command(ctx, command, scope))
+        .collect()
+}
+
+fn parsed_to_command(
+    ctx: &Context,
+    parsed_command: &ParsedCommand,
+    scope: &Scope,
+) -> Result<CommandType, ShellError> {
+    let (name, args) = match parsed_command {
+        ParsedCommand::Call { name, args } => (name, args),
+        ParsedCommand::Literal(literal) => {
+            return Ok(CommandType::Literal(eval_expr(ctx, literal, scope)?))
+        }
+    };
+
+    // a variable at the head of a pipeline, e.g. (g.items | math sum)
+    if args.is_empty() {
+        if let Some(value) = resolve_path(name, scope)? {
+            return Ok(CommandType::Literal(value));
+        }
+    }
+
+    let args = args
+        .iter()
+        .map(|arg| eval_expr(ctx, arg, scope))
+        .collect::<Result<Vec<Value>, ShellError>>()?;
+
+    // a bare directory, e.g. .. or src/, changes into it
+    if args.is_empty() && ctx.is_directory_command(name) {
+        if let Some(cd) = ctx.valid_commands.get("cd") {
+            let internal_command = InternalCommand::new(cd.clone(), vec![Value::string(name)]);
+            return Ok(CommandType::Internal(internal_command));
+        }
+    }
+
+    if let Some(command) = ctx.valid_commands.get(name) {
+        let command = command.clone();
+
+        let internal_command = InternalCommand::new(command, args);
+        Ok(CommandType::Internal(internal_command))
+    } else {
+        let name = name.to_string();
+        let args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+        let external_command = ExternalCommand::new(name, args);
+        Ok(CommandType::External(external_command))
+    }
+}
+
+pub fn eval_expr(ctx: &Context, expr: &Expr, scope: &Scope) -> Result<Value, ShellError> {
+    match expr {
+        Expr::Val(val) => eval_val(ctx, val, scope),
+        Expr::Path(path) => match resolve_path(path, scope)? {
+            Some(value) => Ok(value),
+            None => Ok(Value::string(path)),
+        },
+        Expr::LambdaExpr(param, body) => Ok(Value::Lambda(Lambda {
+            param: param.to_string(),
+            body: *body.clone(),
+            scope: scope.clone(),
+        })),
+        Expr::Binary(lhs, Operator::And, rhs) => {
+            let result =
+                eval_expr(ctx, lhs, scope)?.to_bool()? && eval_expr(ctx, rhs, scope)?.to_bool()?;
+            Ok(Value::bool(result))
+        }
+        Expr::Binary(lhs, Operator::Or, rhs) => {
+            let result =
+                eval_expr(ctx, lhs, scope)?.to_bool()? || eval_expr(ctx, rhs, scope)?.to_bool()?;
+            Ok(Value::bool(result))
+        }
+        Expr::Binary(lhs, op, rhs) => {
+            let lhs = eval_expr(ctx, lhs, scope)?;
+            let rhs = eval_expr(ctx, rhs, scope)?;
+            binary_op(*op, lhs, rhs)
+        }
+        Expr::Not(expr) => Ok(Value::bool(!eval_expr(ctx, expr, scope)?.to_bool()?)),
+        Expr::If(cond, then, otherwise) => {
+            if eval_expr(ctx, cond, scope)?.to_bool()? {
+                eval_expr(ctx, then, scope)
+            } else {
+                match otherwise {
+                    Some(otherwise) => eval_expr(ctx, otherwise, scope),
+                    None => Ok(Value::none()),
+                }
+            }
+        }
+        Expr::Subexpr(pipeline) => run_pipeline(ctx, pipeline, scope),
+        Expr::Command(_, _) | Expr::Assign(_, _) => {
+            Err(ShellError::new(format!("rush : cannot evaluate {}", expr)))
+        }
+    }
+}
+
+fn eval_val(ctx: &Context, val: &Val, scope: &Scope) -> Result<Value, ShellError> {
+    match val {
+        Val::Bool(b) => Ok(Value::bool(*b)),
+        Val::String(s) => Ok(Value::string(s)),
+        Val::Num(n) => Ok(Value::int(*n)),
+        Val::Float(n) => Ok(Value::float(*n)),
+        Val::Size(bytes) => Ok(Value::size(*bytes)),
+        Val::Duration(nanos) => Ok(Value::duration(*nanos)),
+        Val::List(items) => {
+            let values = items
+                .iter()
+                .map(|item| eval_expr(ctx, item, scope))
+                .collect::<Result<Vec<Value>, ShellError>>()?;
+            Ok(Value::list(values))
+        }
+        Val::Record(entries) => {
+            let mut dict = DataDict::default();
+            for (key, value) in entries {
+                dict.insert(key, eval_expr(ctx, value, scope)?);
+            }
+            Ok(Value::object(dict))
+        }
+        Val::Range(start, end) => Ok(Value::range(*start, *end)),
+    }
+}
+
+// Looks up f.size style paths, None if the root is not a variable
+fn resolve_path(path: &str, scope: &Scope) -> Result<Option<Value>, ShellError> {
+    let mut fields = path.split('.');
+
+    let root = fields.next().unwrap_or_default();
+    let mut value = match scope.get(root) {
+        Some(value) => value.clone(),
+        None => return Ok(None),
+    };
+
+    for field in fields {
+        value = value.get_field(field)?;
+    }
+
+    Ok(Some(value))
+}
+
+/// Applies an operator to two evaluated operands
+pub fn binary_op(op: Operator, lhs: Value, rhs: Value) -> Result<Value, ShellError> {
+    match op {
+        Operator::Eq => Ok(Value::bool(values_equal(&lhs, &rhs))),
+        Operator::NotEq => Ok(Value::bool(!values_equal(&lhs, &rhs))),
+        Operator::Lt | Operator::Le | Operator::Gt | Operator::Ge => {
+            let ordering = match (&lhs, &rhs) {
+                (Value::Primitive(a), Value::Primitive(b)) => compare_primitives(a, b),
+                _ => None,
+            };
+            let ordering = ordering.ok_or_else(|| type_error(op, &lhs, &rhs))?;
+
+            let result = match op {
+                Operator::Lt => ordering.is_lt(),
+                Operator::Le => ordering.is_le(),
+                Operator::Gt => ordering.is_gt(),
+                _ => ordering.is_ge(),
+            };
+            Ok(Value::bool(result))
+        }
+        Operator::RegexMatch => match (as_text(&lhs), as_text(&rhs)) {
+            (Some(text), Some(pattern)) => {
+                let regex = Regex::new(&pattern)
+                    .map_err(|err| ShellError::new(format!("rush : invalid regex : {}", err)))?;
+                Ok(Value::bool(regex.is_match(&text)))
+            }
+            _ => Err(type_error(op, &lhs, &rhs)),
+        },
+        Operator::In => match &rhs {
+            Value::Primitive(Primitive::Range(start, end)) => Ok(Value::bool(match lhs {
+                Value::Primitive(Primitive::Integer(n)) => {
+                    (*start.min(end)..=*start.max(end)).contains(&n)
+                }
+                _ => false,
+            })),
+            Value::List(items) => Ok(Value::bool(
+                items.iter().any(|item| values_equal(&lhs, item)),
+            )),
+            Value::Object(dict) => Ok(Value::bool(dict.get(&lhs.to_string()).is_some())),
+            _ => match (as_text(&lhs), as_text(&rhs)) {
+                (Some(needle), Some(haystack)) => Ok(Value::bool(haystack.contains(&*needle))),
+                _ => Err(type_error(op, &lhs, &rhs)),
+            },
+        },
+        // a range joins a list as its integers, e.g. [0] + 1..3
+        _ => match (lhs, rhs) {
+            (lhs @ Value::List(_), rhs) | (lhs, rhs @ Value::List(_)) if op == Operator::Add => {
+                match (lhs.expand_range()?, rhs.expand_range()?) {
+                    (Value::List(mut a), Value::List(b)) => {
+                        a.extend(b);
+                        Ok(Value::list(a))
+                    }
+                    (lhs, rhs) => Err(type_error(op, &lhs, &rhs)),
+                }
+            }
+            (Value::Primitive(a), Value::Primitive(b)) => arithmetic(op, a, b),
+            (lhs, rhs) => Err(type_error(op, &lhs, &rhs)),
+        },
+    }
+}
+
+fn as_text(value: &Value) -> Option<Cow<'_, str>> {
+    match value {
+        Value::Primitive(primitive) => primitive.as_str(),
+        _ => None,
+    }
+}
+
+fn values_equal(lhs: &Value, rhs: &Value) -> bool {
+    match (lhs, rhs) {
+        (Value::Primitive(a), Value::Primitive(b)) => {
+            compare_primitives(a, b).map_or(false, |o| o.is_eq())
+        }
+        _ => lhs == rhs,
+    }
+}
+
+// Times compare with durations by age, so f.modified < 2day means modified in the last 2 days.
+// Sizes compare with plain integers as bytes.
+fn compare_primitives(lhs: &Primitive, rhs: &Primitive) -> Option<Ordering> {
+    let now = SystemTime::now();
+    match (lhs, rhs) {
+        (Primitive::Size(a), Primitive::Integer(b)) => Some((*a as i128).cmp(&(*b as i128))),
+        (Primitive::Integer(a), Primitive::Size(b)) => Some((*a as i128).cmp(&(*b as i128))),
+        (Primitive::Date(date), Primitive::Duration(_)) => {
+            let age = Local::now().signed_duration_since(*date);
+            Primitive::Duration(age.num_nanoseconds()?).compare(rhs)
+        }
+        (Primitive::Duration(_), Primitive::Date(date)) => {
+            let age = Local::now().signed_duration_since(*date);
+            lhs.compare(&Primitive::Duration(age.num_nanoseconds()?))
+        }
+        (Primitive::Time(time), Primitive::Duration(_)) => {
+            Primitive::Duration(nanos_between(time, &now)).compare(rhs)
+        }
+        (Primitive::Duration(_), Primitive::Time(time)) => {
+            lhs.compare(&Primitive::Duration(nanos_between(time, &now)))
+        }
+        _ => lhs.compare(rhs),
+    }
+}
+
+fn nanos_between(start: &SystemTime, end: &SystemTime) -> i64 {
+    match end.duration_since(*start) {
+        Ok(elapsed) => elapsed.as_nanos() as i64,
+        Err(err) => -(err.duration().as_nanos() as i64),
+    }
+}
+
+fn shift_time(time: &SystemTime, nanos: i64) -> Option<Value> {
+    let shift = Duration::from_nanos(nanos.unsigned_abs());
+    let shifted = match nanos >= 0 {
+        true => time.checked_add(shift),
+        false => time.checked_sub(shift),
+    };
+    shifted.map(Value::time)
+}
+
+fn arithmetic(op: Operator, lhs: Primitive, rhs: Primitive) -> Result<Value, ShellError> {
+    match (&lhs, &rhs) {
+        (Primitive::Integer(a), Primitive::Integer(b)) => int_arithmetic(op, *a, *b),
+        (
+            Primitive::Integer(_) | Primitive::Float(_),
+            Primitive::Integer(_) | Primitive::Float(_),
+        ) => float_arithmetic(op, lhs.as_float(), rhs.as_float()),
+        (Primitive::String(a), Primitive::String(b)) if op == Operator::Add => {
+            Ok(Value::string(format!("{}{}", a, b)))
+        }
+        _ => unit_arithmetic(op, &lhs, &rhs).ok_or_else(|| {
+            type_error(
+                op,
+                &Value::Primitive(lhs.clone()),
+                &Value::Primitive(rhs.clone()),
+            )
+        }),
+    }
+}
+
+// Sizes, durations and times, None if the operation is not supported
+fn unit_arithmetic(op: Operator, lhs: &Primitive, rhs: &Primitive) -> Option<Value> {
+    match (lhs, rhs, op) {
+        (Primitive::Size(a), Primitive::Size(b), Operator::Add) => {
+            a.checked_add(*b).map(Value::size)
+        }
+        (Primitive::Size(a), Primitive::Size(b), Operator::Sub) => {
+            a.checked_sub(*b).map(Value::size)
+        }
+        (Primitive::Size(a), Primitive::Size(b), Operator::Div) if *b != 0 => {
+            Some(Value::float(*a as f64 / *b as f64))
+        }
+        (Primitive::Size(a), Primitive::Integer(b), Operator::Mul)
+        | (Primitive::Integer(b), Primitive::Size(a), Operator::Mul) => u64::try_from(*b)
+            .ok()
+            .and_then(|b| a.checked_mul(b))
+            .map(Value::size),
+        (Primitive::Size(a), Primitive::Integer(b), Operator::Div) => u64::try_from(*b)
+            .ok()
+            .and_then(|b| a.checked_div(b))
+            .map(Value::size),
+        (Primitive::Duration(a), Primitive::Duration(b), Operator::Add) => {
+            a.checked_add(*b).map(Value::duration)
+        }
+        (Primitive::Duration(a), Primitive::Duration(b), Operator::Sub) => {
+            a.checked_sub(*b).map(Value::duration)
+        }
+        (Primitive::Duration(a), Primitive::Duration(b), Operator::Div) if *b != 0 => {
+            Some(Value::float(*a as f64 / *b as f64))
+        }
+        (Primitive::Duration(a), Primitive::Integer(b), Operator::Mul)
+        | (Primitive::Integer(b), Primitive::Duration(a), Operator::Mul) => {
+            a.checked_mul(*b).map(Value::duration)
+        }
+        (Primitive::Duration(a), Primitive::Integer(b), Operator::Div) => {
+            a.checked_div(*b).map(Value::duration)
+        }
+        (Primitive::Time(time), Primitive::Duration(nanos), Operator::Add) => {
+            shift_time(time, *nanos)
+        }
+        (Primitive::Time(time), Primitive::Duration(nanos), Operator::Sub) => {
+            shift_time(time, nanos.checked_neg()?)
+        }
+        (Primitive::Time(a), Primitive::Time(b), Operator::Sub) => {
+            Some(Value::duration(nanos_between(b, a)))
+        }
+        (Primitive::Date(date), Primitive::Duration(nanos), Operator::Add) => date
+            .checked_add_signed(chrono::Duration::nanoseconds(*nanos))
+            .map(Value::date),
+        (Primitive::Date(date), Primitive::Duration(nanos), Operator::Sub) => date
+            .checked_sub_signed(chrono::Duration::nanoseconds(*nanos))
+            .map(Value::date),
+        (
+            Primitive::Date(_) | Primitive::Time(_),
+            Primitive::Date(_) | Primitive::Time(_),
+            Operator::Sub,
+        ) => {
+            let elapsed = lhs.as_date()?.signed_duration_since(rhs.as_date()?);
+            elapsed.num_nanoseconds().map(Value::duration)
+        }
+        _ => None,
+    }
+}
+
+fn int_arithmetic(op: Operator, a: i64, b: i64) -> Result<Value, ShellError> {
+    let result = match op {
+        Operator::Add => a.checked_add(b),
+        Operator::Sub => a.checked_sub(b),
+        Operator::Mul => a.checked_mul(b),
+        Operator::Div | Operator::Mod if b == 0 => {
+            return Err(ShellError::new("rush : division by zero"))
+        }
+        // 7 / 2 == 3.5, but 8 / 2 == 4
+        Operator::Div if a.checked_rem(b).is_some_and(|rem| rem != 0) => {
+            return Ok(Value::float(a as f64 / b as f64))
+        }
+        Operator::Div => a.checked_div(b),
+        Operator::Mod => a.checked_rem(b),
+        Operator::Pow if b < 0 => return Ok(Value::float((a as f64).powf(b as f64))),
+        Operator::Pow => u32::try_from(b).ok().and_then(|b| a.checked_pow(b)),
+        _ => unreachable!(),
+    };
+
+    match result {
+        Some(result) => Ok(Value::int(result)),
+        None => Err(ShellError::new("rush : integer overflow")),
+    }
+}
+
+fn float_arithmetic(op: Operator, a: f64, b: f64) -> Result<Value, ShellError> {
+    let result = match op {
+        Operator::Add => a + b,
+        Operator::Sub => a - b,
+        Operator::Mul => a * b,
+        Operator::Div | Operator::Mod if b == 0.0 => {
+            return Err(ShellError::new("rush : division by zero"))
+        }
+        Operator::Div => a / b,
+        Operator::Mod => a % b,
+        Operator::Pow => a.powf(b),
+        _ => unreachable!(),
+    };
+    Ok(Value::float(result))
+}
+
+fn type_error(op: Operator, lhs: &Value, rhs: &Value) -> ShellError {
+    ShellError::new(format!(
+        "rush : cannot apply {} to {} and {}",
+        op,
+        lhs.type_name(),
+        rhs.type_name()
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parselex::parser::parse;
+
+    fn eval(line: &str) -> Result<Value, ShellError> {
+        let pipeline = parse(line, &|_| false)?;
+        run_pipeline(&Context::default(), &pipeline, &Scope::default())
+    }
+
+    fn error(line: &str) -> String {
+        match eval(line) {
+            Ok(value) => panic!("{} evaluated to {:?}", line, value),
+            Err(err) => err.to_string(),
+        }
+    }
+
+    #[test]
+    fn precedence() {
+        assert_eq!(eval("= 1 + 2 * 3").unwrap(), Value::int(7));
+        assert_eq!(eval("= (1 + 2) * 3").unwrap(), Value::int(9));
+        assert_eq!(eval("= 10 - 2 - 3").unwrap(), Value::int(5));
+        assert_eq!(eval("= 2 ** 3 ** 2").unwrap(), Value::int(512));
+        assert_eq!(eval("= 2 * 3 ** 2").unwrap(), Value::int(18));
+        assert_eq!(eval("= 7 / 2").unwrap(), Value::float(3.5));
+        assert_eq!(eval("= 1 + 2 == 3").unwrap(), Value::bool(true));
+    }
+
+    #[test]
+    fn word_operators() {
+        assert_eq!(eval("= 2 in [1 2 3]").unwrap(), Value::bool(true));
+        assert_eq!(eval("= 4 in [1 2 3]").unwrap(), Value::bool(false));
+        assert_eq!(eval("= 3 in 1..5").unwrap(), Value::bool(true));
+        assert_eq!(eval("= true and false").unwrap(), Value::bool(false));
+        assert_eq!(eval("= false or true").unwrap(), Value::bool(true));
+        // and binds tighter than or, not tighter than both
+        assert_eq!(eval("= true or true and false").unwrap(), Value::bool(true));
+        assert_eq!(eval("= not false and false").unwrap(), Value::bool(false));
+        assert_eq!(eval("= 1 < 2 and 2 in [2]").unwrap(), Value::bool(true));
+    }
+
+    #[test]
+    fn negative_numbers() {
+        assert_eq!(eval("= -3 + 1").unwrap(), Value::int(-2));
+        assert_eq!(eval("= 5 - 3").unwrap(), Value::int(2));
+        assert_eq!(eval("= 5 - -3").unwrap(), Value::int(8));
+        assert_eq!(eval("= -2 ** 2").unwrap(), Value::int(4));
+        // -3 right after a number is a literal, not a subtraction
+        assert!(eval("= 5 -3").is_err());
+    }
+
+    #[test]
+    fn overflow() {
+        assert_eq!(
+            error("= 9223372036854775807 + 1"),
+            "rush : integer overflow"
+        );
+        assert_eq!(
+            error("= -9223372036854775808 / -1"),
+            "rush : integer overflow"
+        );
+        assert_eq!(
+            error("= -9223372036854775808 % -1"),
+            "rush : integer overflow"
+        );
+        assert_eq!(error("= 2 ** 64"), "rush : integer overflow");
+    }
+
+    #[test]
+    fn division_by_zero() {
+        assert_eq!(error("= 1 / 0"), "rush : division by zero");
+        assert_eq!(error("= 1 % 0"), "rush : division by zero");
+        assert_eq!(error("= 1.5 / 0.0"), "rush : division by zero");
+    }
+}
@@ -0,0 +1,335 @@
+use std::path::Path;
+
+use crate::{
+    error::ShellError,
+    types::{
+        datadict::DataDict,
+        primary::{Primitive, Value},
+    },
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Json,
+    Csv,
+    Tsv,
+    Toml,
+    Yaml,
+}
+
+impl Format {
+    pub fn from_name(name: &str) -> Option<Format> {
+        match name.to_lowercase().as_str() {
+            "json" => Some(Format::Json),
+            "csv" => Some(Format::Csv),
+            "tsv" => Some(Format::Tsv),
+            "toml" => Some(Format::Toml),
+            "yaml" | "yml" => Some(Format::Yaml),
+            _ => None,
+        }
+    }
+
+    pub fn from_path(path: &Path) -> Option<Format> {
+        Format::from_name(&path.extension()?.to_string_lossy())
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Format::Json => "json",
+            Format::Csv => "csv",
+            Format::Tsv => "tsv",
+            Format::Toml => "toml",
+            Format::Yaml => "yaml",
+        }
+    }
+
+    pub fn parse(&self, text: &str) -> Result<Value, ShellError> {
+        let error = |err: &dyn std::fmt::Display| {
+            ShellError::new(format!("rush : could not parse {} : {}", self.name(), err))
+        };
+
+        match self {
+            Format::Json => serde_json::from_str(text)
+                .map(|json| from_json(&json))
+                .map_err(|err| error(&err)),
+            Format::Csv => from_delimited(text, b',').map_err(|err| error(&err)),
+            Format::Tsv => from_delimited(text, b'\t').map_err(|err| error(&err)),
+            Format::Toml => toml::from_str(text)
+                .map(|toml| from_toml(&toml))
+                .map_err(|err| error(&err)),
+            Format::Yaml => serde_yaml::from_str(text)
+                .map(|yaml| from_yaml(&yaml))
+                .map_err(|err| error(&err)),
+        }
+    }
+
+    pub fn serialize(&self, value: &Value) -> Result<String, ShellError> {
+        let error = |err: &dyn std::fmt::Display| {
+            ShellError::new(format!("rush : could not write {} : {}", self.name(), err))
+        };
+
+        match self {
+            Format::Json => {
+                serde_json::to_string_pretty(&to_json(value)?).map_err(|err| error(&err))
+            }
+            Format::Csv => to_delimited(value, b','),
+            Format::Tsv => to_delimited(value, b'\t'),
+            Format::Toml => match to_toml(value)? {
+                Some(table @ toml::Value::Table(_)) => {
+                    toml::to_string(&table).map_err(|err| error(&err))
+                }
+                _ => Err(ShellError::new("rush : to toml expects a record")),
+            },
+            Format::Yaml => serde_yaml::to_string(&to_json(value)?).map_err(|err| error(&err)),
+        }
+    }
+}
+
+fn record(entries: impl Iterator<Item = (String, Value)>) -> Value {
+    let mut dict = DataDict::default();
+    for (key, value) in entries {
+        dict.insert(key, value);
+    }
+    Value::object(dict)
+}
+
+fn from_json(json: &serde_json::Value) -> Value {
+    match json {
+        serde_json::Value::Null => Value::none(),
+        serde_json::Value::Bool(b) => Value::bool(*b),
+        serde_json::Value::Number(n) => match n.as_i64() {
+            Some(i) => Value::int(i),
+            None => Value::float(n.as_f64().unwrap_or(f64::NAN)),
+        },
+        serde_json::Value::String(s) => Value::string(s),
+        serde_json::Value::Array(items) => {
+            Value::list(items.iter().map(from_json).collect::<Vec<_>>())
+        }
+        serde_json::Value::Object(map) => record(
+            map.iter()
+                .map(|(key, value)| (key.clone(), from_json(value))),
+        ),
+    }
+}
+
+fn from_toml(toml: &toml::Value) -> Value {
+    match toml {
+        toml::Value::String(s) => Value::string(s),
+        toml::Value::Integer(i) => Value::int(*i),
+        toml::Value::Float(n) => Value::float(*n),
+        toml::Value::Boolean(b) => Value::bool(*b),
+        toml::Value::Datetime(date) => {
+            let text = date.to_string();
+            match chrono::DateTime::parse_from_rfc3339(&text) {
+                Ok(date) => Value::date(date),
+                // local dates and times have no offset
+                Err(_) => Value::string(text),
+            }
+        }
+        toml::Value::Array(items) => Value::list(items.iter().map(from_toml).collect::<Vec<_>>()),
+        toml::Value::Table(table) => record(
+            table
+                .iter()
+                .map(|(key, value)| (key.clone(), from_toml(value))),
+        ),
+    }
+}
+
+fn from_yaml(yaml: &serde_yaml::Value) -> Value {
+    match yaml {
+        serde_yaml::Value::Null => Value::none(),
+        serde_yaml::Value::Bool(b) => Value::bool(*b),
+        serde_yaml::Value::Number(n) => match n.as_i64() {
+            Some(i) => Value::int(i),
+            None => Value::float(n.as_f64().unwrap_or(f64::NAN)),
+        },
+        serde_yaml::Value::String(s) => Value::string(s),
+        serde_yaml::Value::Sequence(items) => {
+            Value::list(items.iter().map(from_yaml).collect::<Vec<_>>())
+        }
+        serde_yaml::Value::Mapping(map) => record(map.iter().map(|(key, value)| {
+            let key = match key {
+                serde_yaml::Value::String(s) => s.clone(),
+                other => from_yaml(other).to_string(),
+            };
+            (key, from_yaml(value))
+        })),
+        serde_yaml::Value::Tagged(tagged) => from_yaml(&tagged.value),
+    }
+}
+
+// Only canonical numbers are typed, so 02134, nan and inf stay strings and round trip as they were
+fn is_canonical_number(text: &str) -> bool {
+    let all_digits = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+    let unsigned = text.strip_prefix('-').unwrap_or(text);
+    let (int, frac) = match unsigned.split_once('.') {
+        Some((int, frac)) => (int, Some(frac)),
+        None => (unsigned, None),
+    };
+    let frac_ok = match frac {
+        Some(frac) => all_digits(frac),
+        None => true,
+    };
+    all_digits(int) && (int == "0" || !int.starts_with('0')) && frac_ok
+}
+
+// Cells are typed as int or float when they are canonical numbers, everything else stays a string
+fn from_delimited(text: &str, delimiter: u8) -> Result<Value, csv::Error> {
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .from_reader(text.as_bytes());
+
+    let headers = reader.headers()?.clone();
+    let mut rows = vec![];
+
+    for row in reader.records() {
+        let row = row?;
+        let cells = headers.iter().zip(row.iter()).map(|(header, cell)| {
+            let value = match is_canonical_number(cell) {
+                // integers too large for an int stay strings rather than lose digits
+                true if !cell.contains('.') => match cell.parse::<i64>() {
+                    Ok(i) => Value::int(i),
+                    Err(_) => Value::string(cell),
+                },
+                true => match cell.parse::<f64>() {
+                    Ok(n) => Value::float(n),
+                    Err(_) => Value::string(cell),
+                },
+                false => Value::string(cell),
+            };
+            (header.to_string(), value)
+        });
+        rows.push(record(cells));
+    }
+
+    Ok(Value::list(rows))
+}
+
+fn to_json(value: &Value) -> Result<serde_json::Value, ShellError> {
+    let json = match value {
+        Value::Object(dict) => {
+            let mut map = serde_json::Map::new();
+            for (key, value) in dict.iter() {
+                map.insert(key.clone(), to_json(value)?);
+            }
+            serde_json::Value::Object(map)
+        }
+        Value::List(items) => {
+            serde_json::Value::Array(items.iter().map(to_json).collect::<Result<Vec<_>, _>>()?)
+        }
+        Value::Primitive(primitive) => match primitive {
+            Primitive::Integer(i) => serde_json::Value::from(*i),
+            Primitive::Float(n) => serde_json::Value::from(*n),
+            Primitive::Bool(b) => serde_json::Value::from(*b),
+            Primitive::Size(bytes) => serde_json::Value::from(*bytes),
+            Primitive::Duration(nanos) => serde_json::Value::from(*nanos),
+            Primitive::Binary(bytes) => serde_json::Value::from(bytes.clone()),
+            Primitive::None => serde_json::Value::Null,
+            _ => serde_json::Value::String(cell_text(primitive)),
+        },
+        Value::Lambda(_) => return Err(ShellError::new("rush : cannot serialize a lambda")),
+    };
+    Ok(json)
+}
+
+// toml has no null, so empty values are left out
+fn to_toml(value: &Value) -> Result<Option<toml::Value>, ShellError> {
+    let toml = match value {
+        Value::Object(dict) => {
+            let mut table = toml::value::Table::new();
+            for (key, value) in dict.iter() {
+                if let Some(value) = to_toml(value)? {
+                    table.insert(key.clone(), value);
+                }
+            }
+            toml::Value::Table(table)
+        }
+        Value::List(items) => {
+            let mut array = vec![];
+            for item in items {
+                array.extend(to_toml(item)?);
+            }
+            toml::Value::Array(array)
+        }
+        Value::Primitive(primitive) => match primitive {
+            Primitive::Integer(i) => toml::Value::Integer(*i),
+            Primitive::Float(n) => toml::Value::Float(*n),
+            Primitive::Bool(b) => toml::Value::Boolean(*b),
+            Primitive::Size(bytes) => match i64::try_from(*bytes) {
+                Ok(bytes) => toml::Value::Integer(bytes),
+                Err(_) => toml::Value::Float(*bytes as f64),
+            },
+            Primitive::Duration(nanos) => toml::Value::Integer(*nanos),
+            Primitive::Time(_) | Primitive::Date(_) => match cell_text(primitive).parse() {
+                Ok(date) => toml::Value::Datetime(date),
+                Err(_) => toml::Value::String(cell_text(primitive)),
+            },
+            Primitive::Binary(bytes) => toml::Value::Array(
+                bytes
+                    .iter()
+                    .map(|b| toml::Value::Integer(*b as i64))
+                    .collect(),
+            ),
+            Primitive::None => return Ok(None),
+            _ => toml::Value::String(cell_text(primitive)),
+        },
+        Value::Lambda(_) => return Err(ShellError::new("rush : cannot serialize a lambda")),
+    };
+    Ok(Some(toml))
+}
+
+// Columns are the union of all row keys, in order of appearance
+fn to_delimited(value: &Value, delimiter: u8) -> Result<String, ShellError> {
+    let rows = match value {
+        Value::List(items) => items.iter().collect::<Vec<_>>(),
+        Value::Object(_) => vec![value],
+        _ => return Err(ShellError::new("rush : expected a table to write")),
+    };
+
+    let mut columns: Vec<&String> = vec![];
+    for row in &rows {
+        match row {
+            Value::Object(dict) => {
+                for (key, _) in dict.iter() {
+                    if !columns.contains(&key) {
+                        columns.push(key);
+                    }
+                }
+            }
+            _ => return Err(ShellError::new("rush : expected a table to write")),
+        }
+    }
+
+    let mut writer = csv::WriterBuilder::new()
+        .delimiter(delimiter)
+        .from_writer(vec![]);
+    let error =
+        |err: csv::Error| ShellError::new(format!("rush : could not write table : {}", err));
+
+    writer.write_record(&columns).map_err(error)?;
+    for row in rows {
+        let cells = columns.iter().map(|column| match row.get_field(column) {
+            Ok(Value::Primitive(primitive)) => Ok(cell_text(&primitive)),
+            Ok(nested) => Ok(to_json(&nested)?.to_string()),
+            Err(_) => Ok("".to_string()),
+        });
+        let cells = cells.collect::<Result<Vec<_>, ShellError>>()?;
+        writer.write_record(&cells).map_err(error)?;
+    }
+
+    let bytes = writer
+        .into_inner()
+        .map_err(|err| ShellError::new(err.to_string()))?;
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+// Plain text of a primitive, times are written as absolute dates
+fn cell_text(primitive: &Primitive) -> String {
+    match primitive {
+        Primitive::Time(_) | Primitive::Date(_) => match primitive.as_date() {
+            Some(date) => date.to_rfc3339(),
+            None => "".to_string(),
+        },
+        _ => primitive.to_string(),
+    }
+}
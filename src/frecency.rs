@@ -0,0 +1,160 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+// once ranks add up to this much, all of them decay so old entries fall out
+const MAX_TOTAL_RANK: f64 = 9000.0;
+
+/// A visited directory, `rank` grows with every visit
+#[derive(Debug, Clone)]
+pub struct Visit {
+    pub path: PathBuf,
+    pub rank: f64,
+    pub last_visit: u64,
+}
+
+impl Visit {
+    /// Rank weighted by how recently the directory was visited, as z does
+    pub fn score(&self, now: u64) -> f64 {
+        let age = now.saturating_sub(self.last_visit);
+        match age {
+            age if age < 60 * 60 => self.rank * 4.0,
+            age if age < 24 * 60 * 60 => self.rank * 2.0,
+            age if age < 7 * 24 * 60 * 60 => self.rank / 2.0,
+            _ => self.rank / 4.0,
+        }
+    }
+}
+
+/// Frecency database of visited directories, kept next to ~/.rush_history
+#[derive(Debug, Default)]
+pub struct Frecency {
+    file: Option<PathBuf>,
+    pub visits: Vec<Visit>,
+}
+
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or_default()
+}
+
+impl Frecency {
+    /// A missing or unreadable database is empty
+    pub fn load() -> Frecency {
+        let file = home::home_dir().map(|home| home.join(".rush_dirs"));
+        let contents = file
+            .as_ref()
+            .and_then(|file| fs::read_to_string(file).ok())
+            .unwrap_or_default();
+
+        // path|rank|time per line, the same layout z uses
+        let visits = contents
+            .lines()
+            .filter_map(|line| {
+                let mut fields = line.rsplitn(3, '|');
+                let last_visit = fields.next()?.parse().ok()?;
+                let rank = fields.next()?.parse().ok()?;
+                let path = PathBuf::from(fields.next()?);
+                Some(Visit {
+                    path,
+                    rank,
+                    last_visit,
+                })
+            })
+            .collect();
+
+        Frecency { file, visits }
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let file = match &self.file {
+            Some(file) => file,
+            None => return Ok(()),
+        };
+
+        let contents: String = self
+            .visits
+            .iter()
+            .map(|visit| {
+                format!(
+                    "{}|{}|{}\n",
+                    visit.path.to_string_lossy(),
+                    visit.rank,
+                    visit.last_visit
+                )
+            })
+            .collect();
+
+        // written aside and renamed, so a crash never leaves half a database
+        let partial = file.with_extension("tmp");
+        fs::write(&partial, contents)?;
+        fs::rename(partial, file)
+    }
+
+    pub fn visit(&mut self, dir: &Path) {
+        let now = now();
+        match self.visits.iter_mut().find(|visit| visit.path == dir) {
+            Some(visit) => {
+                visit.rank += 1.0;
+                visit.last_visit = now;
+            }
+            None => self.visits.push(Visit {
+                path: dir.to_path_buf(),
+                rank: 1.0,
+                last_visit: now,
+            }),
+        }
+
+        let total: f64 = self.visits.iter().map(|visit| visit.rank).sum();
+        if total > MAX_TOTAL_RANK {
+            for visit in self.visits.iter_mut() {
+                visit.rank *= 0.99;
+            }
+            self.visits.retain(|visit| visit.rank >= 1.0);
+        }
+    }
+
+    /// Existing directories containing every fragment in order, best score first
+    pub fn matches(&self, fragments: &[String]) -> Vec<(Visit, f64)> {
+        let now = now();
+        let fragments: Vec<String> = fragments.iter().map(|f| f.to_lowercase()).collect();
+
+        let mut matches: Vec<(Visit, f64)> = self
+            .visits
+            .iter()
+            .filter(|visit| {
+                let path = visit.path.to_string_lossy().to_lowercase();
+                let mut rest = path.as_str();
+                fragments
+                    .iter()
+                    .all(|fragment| match rest.find(fragment.as_str()) {
+                        Some(index) => {
+                            rest = &rest[index + fragment.len()..];
+                            true
+                        }
+                        None => false,
+                    })
+            })
+            .filter(|visit| visit.path.is_dir())
+            .map(|visit| (visit.clone(), visit.score(now)))
+            .collect();
+
+        matches.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+        matches
+    }
+}
+
+/// Records a visit made by cd, the home directory is not worth jumping to
+pub fn record_visit(dir: &Path) {
+    if home::home_dir().as_deref() == Some(dir) {
+        return;
+    }
+    let mut frecency = Frecency::load();
+    frecency.visit(dir);
+    // a read only home shouldn't break cd
+    let _ = frecency.save();
+}
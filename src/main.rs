@@ -4,8 +4,13 @@ mod completion;
 mod context;
 mod environment;
 mod error;
+mod eval;
+mod formats;
+mod frecency;
 mod highlight;
+mod interrupt;
 mod parselex;
+mod paths;
 mod rushhelper;
 mod stream;
 mod types;
@@ -1,3 +1,5 @@
+use core::fmt;
+
 use chumsky::prelude::*;
 use chumsky::{
     primitive::{filter, just},
@@ -6,25 +8,209 @@ use chumsky::{
 
 use super::Span;
 
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum Operator {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Mod,
+    Pow,
+    Eq,
+    NotEq,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    RegexMatch,
+    In,
+    And,
+    Or,
+}
+
+impl Operator {
+    // word operators are lexed as items, the parser decides if they are operators
+    pub fn from_keyword(word: &str) -> Option<Operator> {
+        match word {
+            "in" => Some(Operator::In),
+            "and" => Some(Operator::And),
+            "or" => Some(Operator::Or),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Operator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let symbol = match self {
+            Operator::Add => "+",
+            Operator::Sub => "-",
+            Operator::Mul => "*",
+            Operator::Div => "/",
+            Operator::Mod => "%",
+            Operator::Pow => "**",
+            Operator::Eq => "==",
+            Operator::NotEq => "!=",
+            Operator::Lt => "<",
+            Operator::Le => "<=",
+            Operator::Gt => ">",
+            Operator::Ge => ">=",
+            Operator::RegexMatch => "=~",
+            Operator::In => "in",
+            Operator::And => "and",
+            Operator::Or => "or",
+        };
+        write!(f, "{}", symbol)
+    }
+}
+
+// bytes per unit, decimal (KB) and binary (KiB)
+fn size_unit(unit: &str) -> Option<f64> {
+    let bytes = match unit.to_lowercase().as_str() {
+        "b" => 1u64,
+        "kb" => 1000,
+        "mb" => 1000u64.pow(2),
+        "gb" => 1000u64.pow(3),
+        "tb" => 1000u64.pow(4),
+        "pb" => 1000u64.pow(5),
+        "kib" => 1 << 10,
+        "mib" => 1 << 20,
+        "gib" => 1 << 30,
+        "tib" => 1 << 40,
+        "pib" => 1 << 50,
+        _ => return None,
+    };
+    Some(bytes as f64)
+}
+
+// nanoseconds per unit
+fn duration_unit(unit: &str) -> Option<f64> {
+    let nanos = match unit {
+        "ns" => 1i64,
+        "us" => 1_000,
+        "ms" => 1_000_000,
+        "sec" => 1_000_000_000,
+        "min" => 60 * 1_000_000_000,
+        "hr" => 60 * 60 * 1_000_000_000,
+        "day" => 24 * 60 * 60 * 1_000_000_000,
+        "wk" => 7 * 24 * 60 * 60 * 1_000_000_000,
+        _ => return None,
+    };
+    Some(nanos as f64)
+}
+
+fn unit_literal(magnitude: f64, unit: &str) -> Option<Token> {
+    if let Some(bytes) = size_unit(unit) {
+        return match magnitude >= 0.0 {
+            true => Some(Token::Size((magnitude * bytes).round() as u64)),
+            false => None,
+        };
+    }
+    duration_unit(unit).map(|nanos| Token::Duration((magnitude * nanos).round() as i64))
+}
+
+/// Parses unit literals outside the lexer, e.g. "1.5 GB" or "10sec"
+pub fn parse_unit_literal(text: &str) -> Option<Token> {
+    let text = text.trim();
+    let split = text.find(|c: char| c.is_ascii_alphabetic())?;
+    let (magnitude, unit) = text.split_at(split);
+    unit_literal(magnitude.trim().parse().ok()?, unit)
+}
+
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
 pub enum Token {
     Num(i64),
+    Float(String),
+    Size(u64),
+    // nanoseconds
+    Duration(i64),
+    Range(i64, i64),
     Item(String),
     OpenQuote,
     QuotedItem(String),
     Pipe,
     Arrow,
-    Dot,
     Whitespace,
     Equal,
+    Op(Operator),
+    OpenBracket,
+    CloseBracket,
+    OpenBrace,
+    CloseBrace,
+    OpenParen,
+    CloseParen,
+    Comma,
     None,
 }
 
 pub fn lexer() -> impl Parser<char, Vec<(Token, Span)>, Error = Simple<char>> {
-    let number = text::int::<_, Simple<char>>(10).map(|s| Token::Num(s.parse().unwrap()));
+    let is_word_char = |c: &char| {
+        !c.is_whitespace() && !matches!(c, '|' | '=' | '[' | ']' | '{' | '}' | '(' | ')' | ',')
+    };
+
+    // numbers and operators must not run into a word, so 50MB and *.rs stay items
+    let word_end = filter::<_, _, Simple<char>>(move |c: &char| !is_word_char(c))
+        .ignored()
+        .or(end())
+        .rewind();
+    let operator_end = filter::<_, _, Simple<char>>(|c: &char| c.is_whitespace())
+        .ignored()
+        .or(end())
+        .rewind();
+
+    let integer = just('-')
+        .or_not()
+        .then(text::int::<_, Simple<char>>(10))
+        .map(|(sign, digits)| match sign {
+            Some(_) => format!("-{}", digits),
+            None => digits,
+        });
+
+    let number = integer.then_ignore(word_end.clone()).try_map(|s, span| {
+        s.parse()
+            .map(Token::Num)
+            .map_err(|_| Simple::custom(span, "integer out of range"))
+    });
 
-    let is_word_char = |c: &char| *c != ' ' && *c != '=';
+    let float = integer
+        .then_ignore(just('.'))
+        .then(text::digits(10))
+        .then_ignore(word_end.clone())
+        .map(|(int, frac)| Token::Float(format!("{}.{}", int, frac)));
 
+    // 50MB, 2KiB, 1.5GB, 3hr, 10sec
+    let unit = integer
+        .then(just('.').ignore_then(text::digits(10)).or_not())
+        .then(
+            filter::<_, _, Simple<char>>(|c: &char| c.is_ascii_alphabetic())
+                .repeated()
+                .at_least(1)
+                .collect::<String>(),
+        )
+        .then_ignore(word_end.clone())
+        .try_map(|((int, frac), unit), span| {
+            let magnitude = match frac {
+                Some(frac) => format!("{}.{}", int, frac),
+                None => int,
+            };
+            magnitude
+                .parse()
+                .ok()
+                .and_then(|magnitude| unit_literal(magnitude, &unit))
+                .ok_or_else(|| Simple::custom(span, "unknown unit"))
+        });
+
+    // inclusive integer range, e.g. 1..10
+    let range = integer
+        .then_ignore(just(".."))
+        .then(integer)
+        .then_ignore(word_end)
+        .try_map(|(start, end), span| match (start.parse(), end.parse()) {
+            (Ok(start), Ok(end)) => Ok(Token::Range(start, end)),
+            _ => Err(Simple::custom(span, "range bound out of range")),
+        });
+
+    // words may start with dots too, e.g. .., ./src or .config
     let item = filter::<_, _, Simple<char>>(move |c: &char| is_word_char(c))
         .repeated()
         .at_least(1)
@@ -39,26 +225,98 @@ pub fn lexer() -> impl Parser<char, Vec<(Token, Span)>, Error = Simple<char>> {
 
     let open_quote = just('"').to(Token::OpenQuote);
 
+    let operator = choice((
+        just("**").to(Operator::Pow),
+        just("==").to(Operator::Eq),
+        just("!=").to(Operator::NotEq),
+        just("=~").to(Operator::RegexMatch),
+        just("<=").to(Operator::Le),
+        just(">=").to(Operator::Ge),
+        just("+").to(Operator::Add),
+        just("-").to(Operator::Sub),
+        just("*").to(Operator::Mul),
+        just("/").to(Operator::Div),
+        just("%").to(Operator::Mod),
+        just("<").to(Operator::Lt),
+        just(">").to(Operator::Gt),
+    ))
+    .then_ignore(operator_end)
+    .map(Token::Op);
+
     let pipe = just("|").to(Token::Pipe);
     let arrow = just("->").to(Token::Arrow);
-    let dot = just(".").to(Token::Dot);
     let equal = just("=").to(Token::Equal);
 
+    let open_bracket = just("[").to(Token::OpenBracket);
+    let close_bracket = just("]").to(Token::CloseBracket);
+    let open_brace = just("{").to(Token::OpenBrace);
+    let close_brace = just("}").to(Token::CloseBrace);
+    let open_paren = just("(").to(Token::OpenParen);
+    let close_paren = just(")").to(Token::CloseParen);
+    let comma = just(",").to(Token::Comma);
+
     let whitespace = filter::<_, _, Simple<char>>(move |c: &char| c.is_whitespace())
         .repeated()
         .at_least(1)
         .to(Token::Whitespace);
 
-    let token = number
+    let token = range
+        .or(unit)
+        .or(float)
+        .or(number)
         .or(quoted_item)
         .or(open_quote)
+        .or(operator)
         .or(pipe)
         .or(arrow)
-        .or(dot)
         .or(equal)
+        .or(open_bracket)
+        .or(close_bracket)
+        .or(open_brace)
+        .or(close_brace)
+        .or(open_paren)
+        .or(close_paren)
+        .or(comma)
         .or(whitespace)
         .or(item)
         .recover_with(skip_then_retry_until([]));
 
     token.map_with_span(|tok, span| (tok, span)).repeated()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokens(line: &str) -> Vec<Token> {
+        lexer()
+            .parse(line)
+            .unwrap()
+            .into_iter()
+            .map(|(token, _)| token)
+            .collect()
+    }
+
+    fn item(text: &str) -> Token {
+        Token::Item(text.to_string())
+    }
+
+    #[test]
+    fn dotted_words_are_items() {
+        assert_eq!(tokens(".config"), [item(".config")]);
+        assert_eq!(tokens(".git/"), [item(".git/")]);
+        assert_eq!(tokens(".."), [item("..")]);
+        assert_eq!(tokens("../lib"), [item("../lib")]);
+        assert_eq!(
+            tokens("open .env"),
+            [item("open"), Token::Whitespace, item(".env")]
+        );
+    }
+
+    #[test]
+    fn words_end_at_whitespace_and_pipes() {
+        assert_eq!(tokens("a\tb"), [item("a"), Token::Whitespace, item("b")]);
+        assert_eq!(tokens("ls|head"), [item("ls"), Token::Pipe, item("head")]);
+        assert_eq!(tokens("src/\n"), [item("src/"), Token::Whitespace]);
+    }
+}
@@ -1,32 +1,50 @@
 use core::fmt;
 
+use chumsky::error::SimpleReason;
 use chumsky::{prelude::*, Stream};
-use chumsky::{
-    primitive::{filter, just},
-    text::TextParser,
-    Parser,
-};
+use chumsky::{primitive::just, Parser};
 
 use derive_new::new;
 
-use super::lex::{lexer, Token};
+use crate::error::ShellError;
+
+use super::lex::{lexer, Operator, Token};
 use super::Span;
 
 #[derive(Debug, Clone)]
 pub enum Val {
     Bool(bool),
     String(String),
-    List(Vec<Val>),
+    List(Vec<Expr>),
+    Record(Vec<(String, Expr)>),
+    Range(i64, i64),
     Num(i64),
+    Float(f64),
+    Size(u64),
+    Duration(i64),
 }
 
 impl fmt::Display for Val {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            Val::Bool(_) => todo!(),
+            Val::Bool(b) => write!(f, "{}", b),
             Val::String(s) => write!(f, "{}", s),
-            Val::List(_) => todo!(),
+            Val::List(items) => {
+                let items: Vec<String> = items.iter().map(|item| item.to_string()).collect();
+                write!(f, "[{}]", items.join(" "))
+            }
+            Val::Record(entries) => {
+                let entries: Vec<String> = entries
+                    .iter()
+                    .map(|(key, value)| format!("{}: {}", key, value))
+                    .collect();
+                write!(f, "{{{}}}", entries.join(", "))
+            }
+            Val::Range(start, end) => write!(f, "{}..{}", start, end),
             Val::Num(n) => write!(f, "{}", n),
+            Val::Float(n) => write!(f, "{}", n),
+            Val::Size(bytes) => write!(f, "{}B", bytes),
+            Val::Duration(nanos) => write!(f, "{}ns", nanos),
         }
     }
 }
@@ -37,75 +55,387 @@ pub enum Expr {
     Assign(String, Box<Expr>),
     LambdaExpr(Val, Box<Expr>),
     Command(Val, Vec<Expr>),
+    // bare word, either a variable (with fields, e.g. f.size) or a string
+    Path(String),
+    Binary(Box<Expr>, Operator, Box<Expr>),
+    Not(Box<Expr>),
+    If(Box<Expr>, Box<Expr>, Option<Box<Expr>>),
+    Subexpr(ParsedPipeline),
 }
 
 impl fmt::Display for Expr {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             Expr::Val(v) => write!(f, "{}", v),
-            Expr::LambdaExpr(_, _) => todo!(),
-            Expr::Command(_, _) => todo!(),
-            Expr::Assign(_, _) => todo!(),
+            Expr::LambdaExpr(param, body) => write!(f, "{} -> {}", param, body),
+            Expr::Command(name, args) => {
+                write!(f, "{}", name)?;
+                for arg in args {
+                    write!(f, " {}", arg)?;
+                }
+                Ok(())
+            }
+            Expr::Assign(name, value) => write!(f, "{} = {}", name, value),
+            Expr::Path(path) => write!(f, "{}", path),
+            Expr::Binary(lhs, op, rhs) => write!(f, "{} {} {}", lhs, op, rhs),
+            Expr::Not(expr) => write!(f, "not {}", expr),
+            Expr::If(cond, then, otherwise) => {
+                write!(f, "if {} {{ {} }}", cond, then)?;
+                match otherwise {
+                    Some(otherwise) => write!(f, " else {{ {} }}", otherwise),
+                    None => Ok(()),
+                }
+            }
+            Expr::Subexpr(pipeline) => write!(f, "({})", pipeline),
         }
     }
 }
 
-#[derive(Debug)]
-pub struct ParsedCommand {
-    pub name: String,
-    pub args: Vec<Expr>,
+#[derive(Debug, Clone)]
+pub enum ParsedCommand {
+    Call { name: String, args: Vec<Expr> },
+    // a literal or expression at the head of a pipeline, e.g. [3 1 2] | sortby
+    Literal(Expr),
 }
 
 impl ParsedCommand {
     fn from_expr(expression: Expr) -> Self {
         match expression {
             Expr::Command(name, args) => match name {
-                Val::String(name) => ParsedCommand { name, args },
+                Val::String(name) => ParsedCommand::Call { name, args },
                 _ => panic!("Failed to parse!"),
             },
-            _ => unreachable!(),
+            literal => ParsedCommand::Literal(literal),
         }
     }
 }
 
-#[derive(Debug, Default, new)]
+impl fmt::Display for ParsedCommand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParsedCommand::Call { name, args } => {
+                write!(f, "{}", name)?;
+                for arg in args {
+                    write!(f, " {}", arg)?;
+                }
+                Ok(())
+            }
+            ParsedCommand::Literal(literal) => write!(f, "{}", literal),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, new)]
 pub struct ParsedPipeline {
     pub commands: Vec<ParsedCommand>,
 }
 
+impl fmt::Display for ParsedPipeline {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let commands: Vec<String> = self.commands.iter().map(|c| c.to_string()).collect();
+        write!(f, "{}", commands.join(" | "))
+    }
+}
+
+// Chain of left associative binary operations, e.g. 1 + 2 - 3
+fn binary<P>(
+    operand: P,
+    operators: &'static [Operator],
+) -> impl Parser<Token, Expr, Error = Simple<Token>> + Clone
+where
+    P: Parser<Token, Expr, Error = Simple<Token>> + Clone,
+{
+    let operator = filter_map(move |span, tok: Token| {
+        let operator = match &tok {
+            Token::Op(op) => Some(*op),
+            Token::Item(word) => Operator::from_keyword(word),
+            _ => None,
+        };
+        match operator {
+            Some(op) if operators.contains(&op) => Ok(op),
+            _ => Err(Simple::expected_input_found(span, Vec::new(), Some(tok))),
+        }
+    });
+
+    operand
+        .clone()
+        .then(operator.then(operand).repeated())
+        .foldl(|lhs, (op, rhs)| Expr::Binary(Box::new(lhs), op, Box::new(rhs)))
+}
+
 // Does not accept whitespace tokens!!!
 fn ast_builder() -> impl Parser<Token, ParsedPipeline, Error = Simple<Token>> {
-    let num_ident = filter_map(|span, tok: Token| match tok {
-        Token::Item(item) => Ok(Val::String(item)),
+    let mut pipeline = Recursive::<'_, Token, ParsedPipeline, Simple<Token>>::declare();
+    let mut expr = Recursive::<'_, Token, Expr, Simple<Token>>::declare();
+
+    let keyword = |word: &str| just(Token::Item(word.to_string()));
+
+    let word = filter_map(|span, tok: Token| match tok {
+        Token::Item(item) => Ok(item),
+        _ => Err(Simple::expected_input_found(span, Vec::new(), Some(tok))),
+    });
+
+    let literal = filter_map(|span, tok: Token| match tok {
+        Token::Item(item) if item == "true" => Ok(Val::Bool(true)),
+        Token::Item(item) if item == "false" => Ok(Val::Bool(false)),
         Token::Num(n) => Ok(Val::Num(n)),
+        Token::Float(n) => n
+            .parse()
+            .map(Val::Float)
+            .map_err(|_| Simple::custom(span, format!("invalid number {}", n))),
+        Token::Size(bytes) => Ok(Val::Size(bytes)),
+        Token::Duration(nanos) => Ok(Val::Duration(nanos)),
+        Token::Range(start, end) => Ok(Val::Range(start, end)),
         Token::QuotedItem(item) => Ok(Val::String(item)),
         _ => Err(Simple::expected_input_found(span, Vec::new(), Some(tok))),
     });
 
-    let args = num_ident.repeated();
+    // commas between elements are optional : [1 2 3] == [1, 2, 3]
+    let separator = just(Token::Comma).or_not();
+
+    let list = expr
+        .clone()
+        .then_ignore(separator.clone())
+        .repeated()
+        .delimited_by(just(Token::OpenBracket), just(Token::CloseBracket))
+        .map(Val::List);
+
+    // record keys are written as `name:`
+    let key = filter_map(|span, tok: Token| match tok {
+        Token::Item(item) if item.len() > 1 && item.ends_with(':') => {
+            Ok(item.trim_end_matches(':').to_string())
+        }
+        _ => Err(Simple::expected_input_found(span, Vec::new(), Some(tok))),
+    });
+
+    let record = key
+        .then(expr.clone())
+        .then_ignore(separator)
+        .repeated()
+        .delimited_by(just(Token::OpenBrace), just(Token::CloseBrace))
+        .map(Val::Record);
+
+    // f -> f.size > 50
+    let lambda = word
+        .clone()
+        .then_ignore(just(Token::Arrow))
+        .then(expr.clone())
+        .map(|(param, body)| Expr::LambdaExpr(Val::String(param), Box::new(body)));
+
+    // (1 + 2) groups an expression, (ls | get name) runs a pipeline
+    let group = expr
+        .clone()
+        .delimited_by(just(Token::OpenParen), just(Token::CloseParen))
+        .try_map(|expr, span| match expr {
+            Expr::Path(_) => Err(Simple::custom(span, "not an expression")),
+            expr => Ok(expr),
+        });
+
+    let subexpr = pipeline
+        .clone()
+        .delimited_by(just(Token::OpenParen), just(Token::CloseParen))
+        .map(Expr::Subexpr);
+
+    let block = expr
+        .clone()
+        .delimited_by(just(Token::OpenBrace), just(Token::CloseBrace));
+
+    // if cond { a } else if cond { b } else { c }
+    let if_expr = recursive(|if_expr| {
+        keyword("if")
+            .ignore_then(expr.clone())
+            .then(block.clone())
+            .then(keyword("else").ignore_then(block.or(if_expr)).or_not())
+            .map(|((cond, then), otherwise)| {
+                Expr::If(Box::new(cond), Box::new(then), otherwise.map(Box::new))
+            })
+    });
+
+    let atom = lambda
+        .clone()
+        .or(literal.map(Expr::Val))
+        .or(list.clone().map(Expr::Val))
+        .or(record.clone().map(Expr::Val))
+        .or(if_expr.clone())
+        .or(group.clone())
+        .or(subexpr.clone())
+        .or(word.map(Expr::Path))
+        .boxed();
+
+    // exponentiation is right associative : 2 ** 3 ** 2 == 2 ** 9
+    let power = atom
+        .clone()
+        .then_ignore(just(Token::Op(Operator::Pow)))
+        .repeated()
+        .then(atom)
+        .foldr(|lhs, rhs| Expr::Binary(Box::new(lhs), Operator::Pow, Box::new(rhs)))
+        .boxed();
+
+    let product = binary(power, &[Operator::Mul, Operator::Div, Operator::Mod]).boxed();
+    let sum = binary(product, &[Operator::Add, Operator::Sub]).boxed();
+    let comparison = binary(
+        sum,
+        &[
+            Operator::Eq,
+            Operator::NotEq,
+            Operator::Lt,
+            Operator::Le,
+            Operator::Gt,
+            Operator::Ge,
+            Operator::RegexMatch,
+            Operator::In,
+        ],
+    )
+    .boxed();
+
+    let negation = recursive(|negation| {
+        keyword("not")
+            .ignore_then(negation)
+            .map(|expr| Expr::Not(Box::new(expr)))
+            .or(comparison)
+    });
+
+    let conjunction = binary(negation, &[Operator::And]).boxed();
+    let disjunction = binary(conjunction, &[Operator::Or]);
+
+    expr.define(disjunction);
+
+    // operators and commas given to commands are plain strings, e.g. cd / or split row ,
+    let operator_arg = filter_map(|span, tok: Token| match tok {
+        Token::Op(op) => Ok(Val::String(op.to_string())),
+        Token::Comma => Ok(Val::String(",".to_string())),
+        _ => Err(Simple::expected_input_found(span, Vec::new(), Some(tok))),
+    });
+
+    let arg = lambda
+        .or(literal.map(Expr::Val))
+        .or(list.map(Expr::Val))
+        .or(record.map(Expr::Val))
+        .or(group)
+        .or(subexpr)
+        .or(word.map(Expr::Path))
+        .or(operator_arg.map(Expr::Val));
 
     // name of command followed by arguments
-    let command = num_ident.then(args).map(|(name, command_args)| {
-        let args_expr = command_args.into_iter().map(Expr::Val).collect();
-        let command_expr = Expr::Command(name, args_expr);
+    let name = word.or(filter_map(|span, tok: Token| match tok {
+        Token::QuotedItem(item) => Ok(item),
+        _ => Err(Simple::expected_input_found(span, Vec::new(), Some(tok))),
+    }));
+
+    let command = name.then(arg.repeated()).map(|(name, args)| {
+        let command_expr = Expr::Command(Val::String(name), args);
         ParsedCommand::from_expr(command_expr)
     });
 
+    // = 3 * 1024
+    let calculation = just(Token::Equal)
+        .ignore_then(expr.clone())
+        .map(ParsedCommand::Literal);
+
+    let element = calculation
+        .or(if_expr.map(ParsedCommand::Literal))
+        .or(command)
+        .or(expr.map(ParsedCommand::Literal));
+
     // commands seperated by a Pipe
-    command
-        .separated_by(just(Token::Pipe))
-        .map(ParsedPipeline::new)
+    pipeline.define(
+        element
+            .separated_by(just(Token::Pipe))
+            .map(ParsedPipeline::new),
+    );
+
+    pipeline.then_ignore(end())
+}
+
+fn parse_error(err: &Simple<Token>) -> ShellError {
+    match (err.reason(), err.found()) {
+        (SimpleReason::Custom(msg), _) => ShellError::new(format!("rush : {}", msg)),
+        (_, Some(token)) => ShellError::new(format!("rush : unexpected {:?}", token)),
+        (_, None) => ShellError::new("rush : unexpected end of input"),
+    }
+}
+
+// Arguments of external commands are words as typed, so a,b and {} reach the process as is
+// and sleep 60sec isn't given 1min. Subexpressions and quoted arguments are still parsed.
+fn external_words(
+    tokens: Vec<(Token, Span)>,
+    chars: &[char],
+    is_external: &dyn Fn(&str) -> bool,
+) -> Vec<(Token, Span)> {
+    let mut words = vec![];
+    let mut command_position = true;
+    let mut external = false;
+    // whether the command around each open paren is external
+    let mut parens = vec![];
+
+    let mut tokens = tokens.into_iter().peekable();
+    while let Some((token, span)) = tokens.next() {
+        let ends_word = match tokens.peek() {
+            Some((next, _)) => matches!(next, Token::Whitespace | Token::Pipe | Token::CloseParen),
+            None => true,
+        };
+        match token {
+            Token::Whitespace => {}
+            Token::Pipe => {
+                command_position = true;
+                external = false;
+            }
+            Token::OpenParen => {
+                parens.push(external);
+                command_position = true;
+                external = false;
+            }
+            Token::CloseParen => {
+                external = parens.pop().unwrap_or(false);
+                command_position = false;
+            }
+            Token::Item(ref name) if command_position => {
+                command_position = false;
+                external = is_external(name);
+            }
+            _ if command_position => command_position = false,
+            Token::QuotedItem(_) | Token::OpenQuote | Token::Item(_) if ends_word => {}
+            _ if external => {
+                // glue tokens up to the next whitespace, e.g. -F, or {}
+                let mut word = String::new();
+                let mut end = span.end;
+                let mut push = |token: &Token, span: &Span| match token {
+                    Token::QuotedItem(item) => word.push_str(item),
+                    _ => word.extend(&chars[span.clone()]),
+                };
+                push(&token, &span);
+                while let Some((next, next_span)) = tokens.peek() {
+                    let closes = matches!(next, Token::CloseParen) && !parens.is_empty();
+                    if matches!(next, Token::Whitespace | Token::Pipe) || closes {
+                        break;
+                    }
+                    push(next, next_span);
+                    end = next_span.end;
+                    tokens.next();
+                }
+                words.push((Token::Item(word), span.start..end));
+                continue;
+            }
+            _ => {}
+        }
+        words.push((token, span));
+    }
+    words
 }
 
-pub fn parse(query: impl Into<String>) -> ParsedPipeline {
+/// Parses a line, `is_external` tells which command names run as external processes
+pub fn parse(
+    query: impl Into<String>,
+    is_external: &dyn Fn(&str) -> bool,
+) -> Result<ParsedPipeline, ShellError> {
     let query: String = query.into();
-    let len = query.chars().count();
+    let chars: Vec<char> = query.chars().collect();
+    let len = chars.len();
 
-    let (tokens, mut err) = lexer().parse_recovery(query);
+    let (tokens, _lex_errors) = lexer().parse_recovery(query);
 
-    // TODO : Error Handling
     let clean_tokens: Vec<(Token, Span)> = match tokens {
-        Some(toks) => toks
+        Some(toks) => external_words(toks, &chars, is_external)
             .into_iter()
             .filter(|(tok, _)| !matches!(tok, Token::Whitespace))
             .collect(),
@@ -115,5 +445,48 @@ pub fn parse(query: impl Into<String>) -> ParsedPipeline {
     let (ast, parse_errors) =
         ast_builder().parse_recovery(Stream::from_iter(len..len + 1, clean_tokens.into_iter()));
 
-    ast.unwrap()
+    match (ast, parse_errors.first()) {
+        (Some(ast), None) => Ok(ast),
+        (_, Some(err)) => Err(parse_error(err)),
+        (None, None) => Err(ShellError::new("rush : failed to parse")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        let pipeline = parse(line, &|name| name == "sleep" || name == "head").unwrap();
+        match &pipeline.commands[0] {
+            ParsedCommand::Call { args, .. } => args.iter().map(|arg| arg.to_string()).collect(),
+            ParsedCommand::Literal(literal) => panic!("{} parsed as {}", line, literal),
+        }
+    }
+
+    #[test]
+    fn external_units_keep_their_text() {
+        assert_eq!(args("sleep 60sec"), ["60sec"]);
+        assert_eq!(args("head -c 1b x.txt"), ["-c", "1b", "x.txt"]);
+        assert_eq!(args("head -c 1.5KB"), ["-c", "1.5KB"]);
+    }
+
+    #[test]
+    fn lone_comma_is_an_argument() {
+        assert_eq!(args("split row ,"), ["row", ","]);
+        assert_eq!(args("split column \",\" a b"), ["column", ",", "a", "b"]);
+    }
+
+    #[test]
+    fn internal_units_are_parsed() {
+        assert_eq!(args("take 60sec"), ["60000000000ns"]);
+    }
+
+    #[test]
+    fn dotted_paths() {
+        let pipeline = parse(".config", &|_| false).unwrap();
+        assert_eq!(pipeline.commands[0].to_string(), ".config");
+        assert_eq!(args("open .env"), [".env"]);
+        assert_eq!(args("cp .bashrc .bashrc.bak"), [".bashrc", ".bashrc.bak"]);
+    }
 }
@@ -0,0 +1,79 @@
+use std::{
+    env,
+    path::{Component, Path, PathBuf},
+};
+
+/// Expands `~` and environment variables, a path with an unknown variable is only tilde expanded
+pub fn expand_path(input: &str) -> PathBuf {
+    let expanded = shellexpand::full(input).unwrap_or_else(|_| shellexpand::tilde(input));
+    PathBuf::from(expanded.as_ref())
+}
+
+/// Expands a path typed by the user and resolves it against `cwd` when relative
+pub fn resolve_path(cwd: &Path, input: &str) -> PathBuf {
+    let expanded = expand_path(input);
+    match expanded.is_absolute() {
+        true => expanded,
+        false => cwd.join(expanded),
+    }
+}
+
+/// Resolves a `cd` target, a relative name missing from `cwd` is looked up in each `CDPATH` entry
+pub fn resolve_dir(cwd: &Path, input: &str) -> PathBuf {
+    let direct = resolve_path(cwd, input);
+    // ./src and ../lib are explicitly relative to cwd, as in other shells
+    if direct.is_dir() || expand_path(input).is_absolute() || input.starts_with('.') {
+        return direct;
+    }
+
+    let cdpath = env::var_os("CDPATH").unwrap_or_default();
+    env::split_paths(&cdpath)
+        .map(|entry| resolve_path(cwd, &entry.to_string_lossy()).join(input))
+        .find(|candidate| candidate.is_dir())
+        .unwrap_or(direct)
+}
+
+/// Removes `.` and `..` components without touching the filesystem
+pub fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            // a relative path can't go above where it started
+            Component::ParentDir => match normalized.components().next_back() {
+                Some(Component::Normal(_)) => {
+                    normalized.pop();
+                }
+                Some(Component::RootDir) => {}
+                _ => normalized.push(".."),
+            },
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+/// `path` as seen from `base`, e.g. ../lib/mod.rs, both are expected to be absolute
+pub fn relative_to(path: &Path, base: &Path) -> PathBuf {
+    let path = normalize(path);
+    let base = normalize(base);
+
+    let common = path
+        .components()
+        .zip(base.components())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let mut relative = PathBuf::new();
+    for _ in base.components().skip(common) {
+        relative.push("..");
+    }
+    for component in path.components().skip(common) {
+        relative.push(component);
+    }
+
+    match relative.as_os_str().is_empty() {
+        true => PathBuf::from("."),
+        false => relative,
+    }
+}
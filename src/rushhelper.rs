@@ -2,7 +2,8 @@ use rustyline::hint::HistoryHinter;
 use rustyline::CompletionType;
 use rustyline::{highlight::Highlighter};
 use std::borrow::Cow::Owned;
-use std::path::{self, PathBuf};
+use std::cell::RefCell;
+use std::path;
 use std::{env, fs};
 
 use colored::*;
@@ -13,6 +14,7 @@ use crate::context::Context;
 use crate::error::ShellError;
 use crate::parselex::lex::Token;
 use crate::parselex::{self};
+use crate::paths::resolve_path;
 
 #[derive(Helper, Completer, Hinter, Validator)]
 pub struct RushHelper {
@@ -22,6 +24,7 @@ pub struct RushHelper {
     pub hinter: HistoryHinter,
     pub context: Context,
     pub path_checker: PathChecker,
+    pub directories: DirectoryCache,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -47,14 +50,7 @@ impl PathChecker {
             None => ("", prefix),
         };
 
-        let search_dir = PathBuf::from(search_dir);
-
-        let target_dir = if search_dir.is_absolute() || search_dir.starts_with("~") {
-            let expanded = format!("{}", shellexpand::tilde(search_dir.to_str().unwrap()));
-            PathBuf::from(expanded)
-        } else {
-            cwd.join(search_dir)
-        };
+        let target_dir = resolve_path(&cwd, search_dir);
 
         let entries = fs::read_dir(target_dir)?;
         for entry in entries {
@@ -74,6 +70,30 @@ impl PathChecker {
     }
 }
 
+/// Whether the command being typed is a directory, checked once rather than on every keystroke
+#[derive(Default)]
+pub struct DirectoryCache {
+    last: RefCell<Option<(String, bool)>>,
+}
+
+impl DirectoryCache {
+    fn is_directory_command(&self, context: &Context, name: &str) -> bool {
+        if let Some((cached, is_dir)) = &*self.last.borrow() {
+            if cached == name {
+                return *is_dir;
+            }
+        }
+        let is_dir = context.is_directory_command(name);
+        self.last.replace(Some((name.to_string(), is_dir)));
+        is_dir
+    }
+
+    /// A submitted line may have created or removed the directory
+    pub fn clear(&self) {
+        self.last.take();
+    }
+}
+
 impl Highlighter for RushHelper {
     fn highlight_hint<'h>(&self, hint: &'h str) -> std::borrow::Cow<'h, str> {
         let colored = hint.truecolor(140, 140, 140);
@@ -91,12 +111,22 @@ impl Highlighter for RushHelper {
             let slice = &line[span];
 
             let (colored_slice, new_state) = match token {
-                Token::Num(_) => (slice.blue(), state),
+                Token::Num(_)
+                | Token::Float(_)
+                | Token::Range(_, _)
+                | Token::Size(_)
+                | Token::Duration(_) => (slice.blue(), state),
                 Token::Item(_) => match state {
-                    LexState::Command => match self.context.command_exists(slice) {
-                        true => (slice.white().bold(), LexState::Arg),
-                        false => (slice.bright_red(), LexState::Arg),
-                    },
+                    LexState::Command if self.context.command_exists(slice) => {
+                        (slice.white().bold(), LexState::Arg)
+                    }
+                    // directories typed as a command are cd'd into
+                    LexState::Command
+                        if self.directories.is_directory_command(&self.context, slice) =>
+                    {
+                        (slice.cyan().bold().underline(), LexState::Arg)
+                    }
+                    LexState::Command => (slice.bright_red(), LexState::Arg),
                     LexState::Arg => match self.path_checker.valid_path_prefix(slice) {
                         true => (slice.cyan().underline(), state),
                         false => (slice.cyan(), state),
@@ -104,12 +134,12 @@ impl Highlighter for RushHelper {
                     LexState::Quoting => (slice.red(), state),
                 },
                 Token::Pipe => (slice.blue().bold(), LexState::Command),
+                Token::OpenParen => (slice.normal(), LexState::Command),
                 Token::Arrow => (slice.red().bold(), state),
-                Token::Dot => (slice.magenta(), state),
                 Token::Whitespace => (slice.normal(), state),
                 Token::QuotedItem(_) => (slice.bright_green(), state),
                 Token::OpenQuote => (slice.red(), LexState::Quoting),
-                Token::Equal => (slice.blue().bold(), state),
+                Token::Equal | Token::Op(_) => (slice.blue().bold(), state),
                 _ => (slice.normal(), state),
             };
             state = new_state;
@@ -2,9 +2,15 @@ use std::borrow::Borrow;
 
 use indexmap::IndexMap;
 
-use super::{descriptor::Descriptor, primary::Value};
+use super::{
+    descriptor::Descriptor,
+    primary::{Primitive, Value},
+};
 
-#[derive(Debug, Eq, PartialEq)]
+// returned for columns a row doesn't have, e.g. when rows of a table differ
+static NONE: Value = Value::Primitive(Primitive::None);
+
+#[derive(Debug, Clone, Eq, PartialEq)]
 pub struct DataDict {
     dict: IndexMap<String, Value>,
 }
@@ -25,15 +31,40 @@ impl Ord for DataDict {
 
 impl DataDict {
     pub fn default() -> Self {
-        let mut dict = IndexMap::new();
-        dict.insert("none".to_string(), Value::none());
-        DataDict { dict }
+        DataDict {
+            dict: IndexMap::new(),
+        }
     }
 
     pub fn insert(&mut self, name: impl Into<String>, value: Value) {
         self.dict.insert(name.into(), value);
     }
 
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.dict.get(key)
+    }
+
+    pub fn remove(&mut self, key: &str) -> Option<Value> {
+        self.dict.shift_remove(key)
+    }
+
+    /// Renames a key in place, keeping its position
+    pub fn rename(&mut self, from: &str, to: impl Into<String>) {
+        let to = to.into();
+        self.dict = std::mem::take(&mut self.dict)
+            .into_iter()
+            .map(|(key, value)| match key == from {
+                true => (to.clone(), value),
+                false => (key, value),
+            })
+            .collect();
+    }
+
+    /// Entries in insertion order
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &Value)> {
+        self.dict.iter()
+    }
+
     pub fn data_descriptors(&self) -> Vec<Descriptor> {
         self.dict
             .iter()
@@ -52,7 +83,7 @@ impl DataDict {
     pub fn get_data_from_key(&self, key: impl Into<String>) -> &Value {
         match self.dict.get(&key.into()) {
             Some(val) => val,
-            None => self.dict.get("none").unwrap(),
+            None => &NONE,
         }
     }
 }
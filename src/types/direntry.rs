@@ -1,21 +1,116 @@
-use std::fs;
+use std::{
+    fs::{self, FileType, Metadata},
+    os::unix::fs::{MetadataExt, PermissionsExt},
+    path::{Path, PathBuf},
+};
+
+use users::{Groups, Users, UsersCache};
 
 use crate::error::ShellError;
 
-use super::{datadict::DataDict, descriptor::Descriptor, primary::Value};
+use super::{datadict::DataDict, primary::Value};
 
-pub fn direntry_dict(entry: fs::DirEntry) -> Result<DataDict, ShellError> {
+/// Row for a file, `owners` is only given for the long format
+pub fn direntry_dict(
+    path: &Path,
+    name: impl Into<PathBuf>,
+    owners: Option<&UsersCache>,
+) -> Result<DataDict, ShellError> {
     let mut dict = DataDict::default();
 
-    let file_name = entry.file_name();
-
-    let metadata = entry.metadata()?;
+    // symlinks are described themselves, not their target
+    let metadata = fs::symlink_metadata(path)?;
     let len = metadata.len();
     let modified = metadata.modified()?;
 
-    dict.insert("name", Value::string(file_name.to_string_lossy()));
+    dict.insert("name", Value::path(name));
+
+    let owners = match owners {
+        Some(owners) => owners,
+        None => {
+            dict.insert("size", Value::size(len));
+            dict.insert("modified", Value::time(modified));
+            return Ok(dict);
+        }
+    };
+
+    let file_type = metadata.file_type();
+    dict.insert("type", Value::string(type_name(&file_type)));
+    dict.insert("mode", Value::string(mode_string(&metadata)));
+    dict.insert("owner", Value::string(owner_name(owners, metadata.uid())));
+    dict.insert("group", Value::string(group_name(owners, metadata.gid())));
     dict.insert("size", Value::size(len));
     dict.insert("modified", Value::time(modified));
+    dict.insert("accessed", time_or_none(metadata.accessed()));
+    dict.insert("created", time_or_none(metadata.created()));
+    dict.insert("inode", Value::int(metadata.ino() as i64));
+    dict.insert("links", Value::int(metadata.nlink() as i64));
+
+    let target = match file_type.is_symlink() {
+        true => fs::read_link(path)
+            .map(Value::path)
+            .unwrap_or_else(|_| Value::none()),
+        false => Value::none(),
+    };
+    dict.insert("target", target);
 
     Ok(dict)
 }
+
+/// file, dir or symlink
+pub fn type_name(file_type: &FileType) -> &'static str {
+    if file_type.is_symlink() {
+        "symlink"
+    } else if file_type.is_dir() {
+        "dir"
+    } else {
+        "file"
+    }
+}
+
+/// Permissions as ls shows them, e.g. drwxr-xr-x
+pub fn mode_string(metadata: &Metadata) -> String {
+    let mode = metadata.permissions().mode();
+    let file_type = metadata.file_type();
+
+    let kind = if file_type.is_symlink() {
+        'l'
+    } else if file_type.is_dir() {
+        'd'
+    } else {
+        '-'
+    };
+
+    let mut mode_string = String::from(kind);
+    for shift in [6, 3, 0] {
+        let bits = (mode >> shift) & 0o7;
+        mode_string.push(if bits & 0o4 != 0 { 'r' } else { '-' });
+        mode_string.push(if bits & 0o2 != 0 { 'w' } else { '-' });
+        mode_string.push(if bits & 0o1 != 0 { 'x' } else { '-' });
+    }
+    mode_string
+}
+
+/// User name of a uid, unknown ids are shown as numbers
+pub fn owner_name(owners: &UsersCache, uid: u32) -> String {
+    match owners.get_user_by_uid(uid) {
+        Some(user) => user.name().to_string_lossy().into_owned(),
+        None => uid.to_string(),
+    }
+}
+
+/// Group name of a gid, unknown ids are shown as numbers
+pub fn group_name(owners: &UsersCache, gid: u32) -> String {
+    match owners.get_group_by_gid(gid) {
+        Some(group) => group.name().to_string_lossy().into_owned(),
+        None => gid.to_string(),
+    }
+}
+
+/// Not every filesystem records creation times
+pub fn time_or_none(time: std::io::Result<std::time::SystemTime>) -> Value {
+    match time {
+        Ok(time) => Value::time(time),
+        Err(_) => Value::none(),
+    }
+}
@@ -1,28 +1,39 @@
 use core::fmt::{self, Debug};
-use std::time::SystemTime;
+use std::{borrow::Cow, cmp::Ordering, path::PathBuf, time::SystemTime};
+
+use chrono::{DateTime, FixedOffset, Local};
 
 use crate::{
     error::ShellError,
-    parselex::parser,
-    utils::{time_to_english, RelativeTime},
+    eval::Lambda,
+    utils::{format_duration, time_to_english, RelativeTime},
     views::baseview::BaseView,
 };
 
 use super::{datadict::DataDict, descriptor::Descriptor};
 
-#[derive(Debug, Ord, Eq, PartialOrd, PartialEq)]
+#[derive(Debug, Clone)]
 pub enum Primitive {
     String(String),
     Integer(i64),
+    Float(f64),
     Time(SystemTime),
+    Date(DateTime<FixedOffset>),
     Size(u64),
+    // nanoseconds
+    Duration(i64),
+    Bool(bool),
+    Path(PathBuf),
+    Binary(Vec<u8>),
+    // inclusive bounds, only expanded into a list when a command consumes it
+    Range(i64, i64),
     None,
 }
 
 impl RelativeTime for SystemTime {
     fn relative_time(&self) -> String {
         let now = SystemTime::now();
-        let duration = now.duration_since(*self).unwrap();
+        let duration = now.duration_since(*self).unwrap_or_default();
         time_to_english(duration.as_secs())
     }
 }
@@ -32,19 +43,132 @@ impl fmt::Display for Primitive {
         match self {
             Primitive::String(s) => write!(f, "{}", s),
             Primitive::Integer(i) => write!(f, "{}", i),
-            Primitive::Time(_) => todo!(),
-            Primitive::Size(_) => todo!(),
-            Primitive::None => todo!(),
+            Primitive::Float(n) => write!(f, "{}", n),
+            Primitive::Bool(b) => write!(f, "{}", b),
+            Primitive::Time(t) => write!(f, "{}", t.relative_time()),
+            Primitive::Date(d) => write!(f, "{}", d.to_rfc3339()),
+            Primitive::Size(bytes) => write!(f, "{}", bytes),
+            Primitive::Duration(nanos) => write!(f, "{}", format_duration(*nanos)),
+            Primitive::Path(p) => write!(f, "{}", p.to_string_lossy()),
+            Primitive::Binary(bytes) => write!(f, "{}", String::from_utf8_lossy(bytes)),
+            Primitive::Range(start, end) => write!(f, "{}..{}", start, end),
+            Primitive::None => Ok(()),
         }
     }
 }
 
+impl PartialEq for Primitive {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Primitive {}
+
+impl PartialOrd for Primitive {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Primitive {
+    // Primitives of different kinds are ordered by kind
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.compare(other)
+            .unwrap_or_else(|| self.kind_order().cmp(&other.kind_order()))
+    }
+}
+
 impl Primitive {
+    /// Compares primitives of the same kind, and kinds sharing a representation:
+    /// integers with floats, strings with paths and times with dates
+    pub fn compare(&self, other: &Primitive) -> Option<Ordering> {
+        match (self, other) {
+            (
+                Primitive::String(_) | Primitive::Path(_),
+                Primitive::String(_) | Primitive::Path(_),
+            ) => Some(self.as_str()?.cmp(&other.as_str()?)),
+            (Primitive::Integer(a), Primitive::Integer(b)) => Some(a.cmp(b)),
+            (Primitive::Float(a), Primitive::Float(b)) => Some(a.total_cmp(b)),
+            (Primitive::Integer(a), Primitive::Float(b)) => Some((*a as f64).total_cmp(b)),
+            (Primitive::Float(a), Primitive::Integer(b)) => Some(a.total_cmp(&(*b as f64))),
+            (Primitive::Time(a), Primitive::Time(b)) => Some(a.cmp(b)),
+            (Primitive::Time(_) | Primitive::Date(_), Primitive::Time(_) | Primitive::Date(_)) => {
+                Some(self.as_date()?.cmp(&other.as_date()?))
+            }
+            (Primitive::Size(a), Primitive::Size(b)) => Some(a.cmp(b)),
+            (Primitive::Duration(a), Primitive::Duration(b)) => Some(a.cmp(b)),
+            (Primitive::Bool(a), Primitive::Bool(b)) => Some(a.cmp(b)),
+            (Primitive::Binary(a), Primitive::Binary(b)) => Some(a.cmp(b)),
+            (Primitive::Range(a, b), Primitive::Range(c, d)) => Some((a, b).cmp(&(c, d))),
+            (Primitive::None, Primitive::None) => Some(Ordering::Equal),
+            _ => None,
+        }
+    }
+
+    fn kind_order(&self) -> u8 {
+        match self {
+            Primitive::String(_) | Primitive::Path(_) => 0,
+            Primitive::Integer(_) | Primitive::Float(_) => 1,
+            Primitive::Time(_) | Primitive::Date(_) => 2,
+            Primitive::Size(_) => 3,
+            Primitive::Duration(_) => 4,
+            Primitive::Bool(_) => 5,
+            Primitive::Binary(_) => 6,
+            Primitive::Range(_, _) => 7,
+            Primitive::None => 8,
+        }
+    }
+
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Primitive::String(_) => "string",
+            Primitive::Integer(_) => "int",
+            Primitive::Float(_) => "float",
+            Primitive::Time(_) => "time",
+            Primitive::Date(_) => "date",
+            Primitive::Size(_) => "filesize",
+            Primitive::Duration(_) => "duration",
+            Primitive::Bool(_) => "bool",
+            Primitive::Path(_) => "path",
+            Primitive::Binary(_) => "binary",
+            Primitive::Range(_, _) => "range",
+            Primitive::None => "nothing",
+        }
+    }
+
+    pub fn as_str(&self) -> Option<Cow<'_, str>> {
+        match self {
+            Primitive::String(s) => Some(Cow::Borrowed(s)),
+            Primitive::Path(p) => Some(p.to_string_lossy()),
+            _ => None,
+        }
+    }
+
+    pub fn as_date(&self) -> Option<DateTime<FixedOffset>> {
+        match self {
+            Primitive::Time(t) => Some(DateTime::<Local>::from(*t).into()),
+            Primitive::Date(d) => Some(*d),
+            _ => None,
+        }
+    }
+
+    pub fn as_float(&self) -> f64 {
+        match self {
+            Primitive::Integer(i) => *i as f64,
+            Primitive::Float(n) => *n,
+            Primitive::Size(bytes) => *bytes as f64,
+            _ => f64::NAN,
+        }
+    }
+
     pub fn format(&self) -> String {
         match self {
             Primitive::String(s) => s.to_string(),
             Primitive::Integer(i) => i.to_string(),
+            Primitive::Float(n) => format_float(*n),
             Primitive::Time(t) => t.relative_time(),
+            Primitive::Date(d) => d.format("%Y-%m-%d %H:%M:%S %:z").to_string(),
             Primitive::Size(bytes) => {
                 let kilobytes = (*bytes as f32) / 1024.0;
                 let megabytes = kilobytes / 1024.0;
@@ -60,46 +184,61 @@ impl Primitive {
                     format!("{:.2} bytes", bytes)
                 }
             }
+            Primitive::Bool(b) => b.to_string(),
+            Primitive::Duration(nanos) => format_duration(*nanos),
+            Primitive::Path(p) => p.to_string_lossy().to_string(),
+            Primitive::Binary(bytes) => {
+                format!("<binary {}>", Primitive::Size(bytes.len() as u64).format())
+            }
+            Primitive::Range(start, end) => format!("{}..{}", start, end),
             Primitive::None => "".to_string(),
         }
     }
 }
 
-#[derive(Debug, Ord, Eq, PartialOrd, PartialEq)]
+// ranges are expanded into at most this many values
+const MAX_RANGE_LEN: u64 = 1_000_000;
+
+/// Integers of an inclusive range, counting down when `end` is below `start`
+pub fn range_values(start: i64, end: i64) -> Box<dyn Iterator<Item = i64>> {
+    match start <= end {
+        true => Box::new(start..=end),
+        false => Box::new((end..=start).rev()),
+    }
+}
+
+// At most two decimals, but always show that the number is a float
+fn format_float(n: f64) -> String {
+    if n.fract() == 0.0 {
+        format!("{:.1}", n)
+    } else {
+        let formatted = format!("{:.2}", n);
+        // 1.999 rounds to 2.00, which still reads as a float
+        match formatted.trim_end_matches('0') {
+            trimmed if trimmed.ends_with('.') => format!("{}0", trimmed),
+            trimmed => trimmed.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Ord, Eq, PartialOrd, PartialEq)]
 pub enum Value {
     Object(DataDict),
     List(Vec<Value>),
     Primitive(Primitive),
+    Lambda(Lambda),
 }
 
 impl fmt::Display for Value {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            Value::Object(_) => todo!(),
-            Value::List(_) => todo!(),
+            Value::Object(_) => write!(f, "{}", self.format()),
+            Value::List(l) => {
+                let items: Vec<String> = l.iter().map(|item| item.to_string()).collect();
+                write!(f, "{}", items.join(" "))
+            }
             Value::Primitive(p) => write!(f, "{}", p),
-        }
-    }
-}
-
-impl From<&parser::Val> for Value {
-    fn from(input: &parser::Val) -> Self {
-        match input {
-            parser::Val::Bool(_) => todo!(),
-            parser::Val::String(s) => Value::string(s),
-            parser::Val::List(_) => todo!(),
-            parser::Val::Num(n) => Value::int(*n),
-        }
-    }
-}
-
-impl From<&parser::Expr> for Value {
-    fn from(input: &parser::Expr) -> Self {
-        match input {
-            parser::Expr::Val(v) => Value::from(v),
-            parser::Expr::LambdaExpr(_, _) => todo!(),
-            parser::Expr::Command(_, _) => todo!(),
-            _ => todo!(),
+            Value::Lambda(l) => write!(f, "{}", l),
         }
     }
 }
@@ -110,6 +249,7 @@ impl Value {
             Value::Object(o) => o.data_descriptors(),
             Value::List(_l) => todo!(),
             Value::Primitive(_p) => todo!(),
+            Value::Lambda(_l) => todo!(),
         }
     }
 
@@ -118,6 +258,7 @@ impl Value {
             Value::Object(o) => o.get_data_from_key(key.into()),
             Value::List(_l) => todo!(),
             Value::Primitive(_p) => todo!(),
+            Value::Lambda(_l) => todo!(),
         }
     }
 
@@ -126,14 +267,53 @@ impl Value {
             Value::Object(o) => o.get_data(desc),
             Value::List(_l) => todo!(),
             Value::Primitive(_p) => todo!(),
+            Value::Lambda(_l) => todo!(),
         }
     }
 
     pub fn format(&self) -> String {
         match self {
-            Value::Object(_o) => todo!(),
-            Value::List(_l) => todo!(),
+            Value::Object(o) => format!("{{{} fields}}", o.data_descriptors().len()),
+            Value::List(l) => format!("[{} items]", l.len()),
             Value::Primitive(p) => p.format(),
+            Value::Lambda(l) => l.to_string(),
+        }
+    }
+
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Value::Object(_) => "record",
+            Value::List(_) => "list",
+            Value::Primitive(p) => p.type_name(),
+            Value::Lambda(_) => "lambda",
+        }
+    }
+
+    /// Field access used by paths such as f.size, lists are indexed or mapped over
+    pub fn get_field(&self, field: &str) -> Result<Value, ShellError> {
+        match self {
+            Value::Object(o) => match o.get(field) {
+                Some(value) => Ok(value.clone()),
+                None => Err(ShellError::new(format!("no column named {}", field))),
+            },
+            Value::List(l) => match field.parse::<usize>() {
+                Ok(index) => match l.get(index) {
+                    Some(value) => Ok(value.clone()),
+                    None => Err(ShellError::new(format!("index {} out of range", index))),
+                },
+                Err(_) => {
+                    let values = l
+                        .iter()
+                        .map(|item| item.get_field(field))
+                        .collect::<Result<Vec<Value>, ShellError>>()?;
+                    Ok(Value::list(values))
+                }
+            },
+            _ => Err(ShellError::new(format!(
+                "cannot access {} on a {}",
+                field,
+                self.type_name()
+            ))),
         }
     }
 
@@ -153,17 +333,62 @@ impl Value {
         Value::Primitive(Primitive::Integer(int.into()))
     }
 
+    pub fn float(float: impl Into<f64>) -> Self {
+        Value::Primitive(Primitive::Float(float.into()))
+    }
+
     pub fn time(time: impl Into<SystemTime>) -> Self {
         Value::Primitive(Primitive::Time(time.into()))
     }
 
+    pub fn date(date: impl Into<DateTime<FixedOffset>>) -> Self {
+        Value::Primitive(Primitive::Date(date.into()))
+    }
+
+    pub fn path(path: impl Into<PathBuf>) -> Self {
+        Value::Primitive(Primitive::Path(path.into()))
+    }
+
+    pub fn binary(bytes: impl Into<Vec<u8>>) -> Self {
+        Value::Primitive(Primitive::Binary(bytes.into()))
+    }
+
     pub fn size(size: impl Into<u64>) -> Self {
         Value::Primitive(Primitive::Size(size.into()))
     }
 
+    pub fn duration(nanos: impl Into<i64>) -> Self {
+        Value::Primitive(Primitive::Duration(nanos.into()))
+    }
+
+    pub fn bool(boolean: bool) -> Self {
+        Value::Primitive(Primitive::Bool(boolean))
+    }
+
+    pub fn range(start: i64, end: i64) -> Self {
+        Value::Primitive(Primitive::Range(start, end))
+    }
+
     pub fn none() -> Self {
         Value::Primitive(Primitive::None)
     }
+
+    /// Turns a range into the list of its integers, other values are unchanged
+    pub fn expand_range(self) -> Result<Self, ShellError> {
+        match self {
+            Value::Primitive(Primitive::Range(start, end)) => {
+                if start.abs_diff(end) >= MAX_RANGE_LEN {
+                    return Err(ShellError::new(format!(
+                        "rush : {}..{} is too long to expand into a list",
+                        start, end
+                    )));
+                }
+                let values: Vec<Value> = range_values(start, end).map(Value::int).collect();
+                Ok(Value::list(values))
+            }
+            other => Ok(other),
+        }
+    }
 }
 
 impl Value {
@@ -173,6 +398,26 @@ impl Value {
             _ => Err(ShellError::new("Expected an integer")),
         }
     }
+
+    pub fn to_float(&self) -> Result<f64, ShellError> {
+        match self {
+            Value::Primitive(p @ (Primitive::Integer(_) | Primitive::Float(_))) => Ok(p.as_float()),
+            _ => Err(ShellError::new(format!(
+                "Expected a number, found {}",
+                self.type_name()
+            ))),
+        }
+    }
+
+    pub fn to_bool(&self) -> Result<bool, ShellError> {
+        match self {
+            Value::Primitive(Primitive::Bool(b)) => Ok(*b),
+            _ => Err(ShellError::new(format!(
+                "Expected a boolean, found {}",
+                self.type_name()
+            ))),
+        }
+    }
 }
 
 pub trait ToBaseView {
@@ -1,17 +1,186 @@
+use std::{
+    fs, io,
+    time::{Duration, SystemTime},
+};
+
+use users::{Users, UsersCache};
+
 use crate::error::ShellError;
 
 use super::{datadict::DataDict, primary::Value};
 
-pub fn process_dict(proc: sysinfo::Process) -> Result<DataDict, ShellError> {
+/// Fields of /proc/<pid>/stat
+#[derive(Debug, Clone)]
+pub struct ProcessInfo {
+    pub pid: i64,
+    pub ppid: i64,
+    pub name: String,
+    pub state: char,
+    pub cpu_ticks: u64,
+    pub threads: i64,
+    pub start_ticks: u64,
+    pub virtual_size: u64,
+    pub resident_pages: u64,
+}
+
+impl ProcessInfo {
+    pub fn read(pid: i64) -> io::Result<ProcessInfo> {
+        let stat = fs::read_to_string(format!("/proc/{}/stat", pid))?;
+        let invalid = || io::Error::new(io::ErrorKind::InvalidData, "malformed stat");
+
+        // the name is in parentheses and may itself contain spaces or parentheses
+        let open = stat.find('(').ok_or_else(invalid)?;
+        let close = stat.rfind(')').ok_or_else(invalid)?;
+        let name = stat[open + 1..close].to_string();
+        let fields: Vec<&str> = stat[close + 1..].split_whitespace().collect();
+
+        let field = |index: usize| -> io::Result<u64> {
+            fields
+                .get(index)
+                .and_then(|field| field.parse().ok())
+                .ok_or_else(invalid)
+        };
+
+        Ok(ProcessInfo {
+            pid,
+            ppid: field(1)? as i64,
+            name,
+            state: fields
+                .first()
+                .and_then(|state| state.chars().next())
+                .ok_or_else(invalid)?,
+            cpu_ticks: field(11)? + field(12)?,
+            threads: field(17)? as i64,
+            start_ticks: field(19)?,
+            virtual_size: field(20)?,
+            resident_pages: field(21)?,
+        })
+    }
+
+    /// Full command line, kernel threads have none and show their name in brackets
+    pub fn command(&self) -> String {
+        let cmdline = fs::read(format!("/proc/{}/cmdline", self.pid)).unwrap_or_default();
+        let args: Vec<String> = cmdline
+            .split(|b| *b == 0)
+            .filter(|arg| !arg.is_empty())
+            .map(|arg| String::from_utf8_lossy(arg).into_owned())
+            .collect();
+
+        match args.is_empty() {
+            true => format!("[{}]", self.name),
+            false => args.join(" "),
+        }
+    }
+
+    pub fn uid(&self) -> Option<u32> {
+        let status = fs::read_to_string(format!("/proc/{}/status", self.pid)).ok()?;
+        let line = status.lines().find(|line| line.starts_with("Uid:"))?;
+        line.split_whitespace().nth(1)?.parse().ok()
+    }
+}
+
+/// All running processes, sorted by pid
+pub fn list_processes() -> Result<Vec<ProcessInfo>, ShellError> {
+    let mut processes = vec![];
+    for entry in fs::read_dir("/proc")? {
+        let pid = match entry?.file_name().to_string_lossy().parse::<i64>() {
+            Ok(pid) => pid,
+            Err(_) => continue,
+        };
+        // processes can exit while being listed
+        if let Ok(process) = ProcessInfo::read(pid) {
+            processes.push(process);
+        }
+    }
+    processes.sort_by_key(|process| process.pid);
+    Ok(processes)
+}
+
+/// System constants needed to make sense of /proc values
+pub struct ProcClock {
+    pub ticks_per_sec: u64,
+    pub page_size: u64,
+    pub boot_time: SystemTime,
+}
+
+impl ProcClock {
+    pub fn read() -> Result<ProcClock, ShellError> {
+        // sysconf only reads configuration values
+        let (ticks, page_size) = unsafe {
+            (
+                libc::sysconf(libc::_SC_CLK_TCK),
+                libc::sysconf(libc::_SC_PAGESIZE),
+            )
+        };
+
+        let stat = fs::read_to_string("/proc/stat")?;
+        let boot_secs = stat
+            .lines()
+            .find_map(|line| line.strip_prefix("btime "))
+            .and_then(|secs| secs.trim().parse().ok())
+            .ok_or_else(|| ShellError::new("ps : could not read boot time"))?;
+
+        Ok(ProcClock {
+            ticks_per_sec: u64::try_from(ticks).unwrap_or(100).max(1),
+            page_size: u64::try_from(page_size).unwrap_or(4096),
+            boot_time: SystemTime::UNIX_EPOCH + Duration::from_secs(boot_secs),
+        })
+    }
+}
+
+fn state_name(state: char) -> &'static str {
+    match state {
+        'R' => "running",
+        'S' => "sleeping",
+        'D' => "disk sleep",
+        'Z' => "zombie",
+        'T' => "stopped",
+        't' => "tracing stop",
+        'I' => "idle",
+        'X' | 'x' => "dead",
+        'P' => "parked",
+        _ => "unknown",
+    }
+}
+
+/// Row for a process, `cpu` and `owners` are only given for the long format
+pub fn process_dict(
+    proc: &ProcessInfo,
+    cpu: Option<f64>,
+    clock: &ProcClock,
+    owners: Option<&UsersCache>,
+) -> Result<DataDict, ShellError> {
     let mut dict = DataDict::default();
 
-    let name = proc.name.to_owned();
-    let pid = proc.pid;
-    let memory = proc.memory;
+    dict.insert("pid", Value::int(proc.pid));
+    dict.insert("ppid", Value::int(proc.ppid));
+    dict.insert("name", Value::string(&proc.name));
+    dict.insert("status", Value::string(state_name(proc.state)));
+    if let Some(cpu) = cpu {
+        dict.insert("cpu", Value::float(cpu));
+    }
+    dict.insert("memory", Value::size(proc.resident_pages * clock.page_size));
+
+    let owners = match owners {
+        Some(owners) => owners,
+        None => return Ok(dict),
+    };
+
+    let user = match proc.uid() {
+        Some(uid) => match owners.get_user_by_uid(uid) {
+            Some(user) => Value::string(user.name().to_string_lossy()),
+            None => Value::string(uid.to_string()),
+        },
+        None => Value::none(),
+    };
+    let start =
+        clock.boot_time + Duration::from_millis(proc.start_ticks * 1000 / clock.ticks_per_sec);
 
-    dict.insert("name", Value::string(name));
-    dict.insert("pid", Value::int(pid));
-    dict.insert("memory", Value::size(memory));
+    dict.insert("virtual", Value::size(proc.virtual_size));
+    dict.insert("threads", Value::int(proc.threads));
+    dict.insert("user", user);
+    dict.insert("start", Value::time(start));
+    dict.insert("command", Value::string(proc.command()));
 
     Ok(dict)
 }
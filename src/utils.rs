@@ -1,3 +1,5 @@
+use std::{cmp::Ordering, iter::Peekable, str::Chars};
+
 pub trait RelativeTime {
     fn relative_time(&self) -> String;
 }
@@ -33,3 +35,105 @@ pub fn time_to_english(seconds: u64) -> String {
         format!("{} years ago", years)
     }
 }
+
+/// Formats nanoseconds using the two largest units, e.g. "1hr 30min"
+pub fn format_duration(nanos: i64) -> String {
+    const UNITS: [(&str, i64); 8] = [
+        ("wk", 7 * 24 * 60 * 60 * 1_000_000_000),
+        ("day", 24 * 60 * 60 * 1_000_000_000),
+        ("hr", 60 * 60 * 1_000_000_000),
+        ("min", 60 * 1_000_000_000),
+        ("sec", 1_000_000_000),
+        ("ms", 1_000_000),
+        ("us", 1_000),
+        ("ns", 1),
+    ];
+
+    if nanos == 0 {
+        return "0sec".to_string();
+    }
+
+    let sign = if nanos < 0 { "-" } else { "" };
+    let mut remaining = nanos.unsigned_abs();
+
+    let parts: Vec<String> = UNITS
+        .iter()
+        .filter_map(|(name, size)| {
+            let count = remaining / *size as u64;
+            remaining %= *size as u64;
+            (count > 0).then(|| format!("{}{}", count, name))
+        })
+        .take(2)
+        .collect();
+
+    format!("{}{}", sign, parts.join(" "))
+}
+
+/// Compares strings with digit runs as numbers, so "file2" < "file10"
+pub fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let (mut a_chars, mut b_chars) = (a.chars().peekable(), b.chars().peekable());
+
+    loop {
+        let (a_digit, b_digit) = match (a_chars.peek(), b_chars.peek()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(x), Some(y)) => (x.is_ascii_digit(), y.is_ascii_digit()),
+        };
+
+        let ordering = match (a_digit, b_digit) {
+            (true, true) => {
+                let a_run = take_digits(&mut a_chars);
+                let b_run = take_digits(&mut b_chars);
+                let (a_num, b_num) = (a_run.trim_start_matches('0'), b_run.trim_start_matches('0'));
+                a_num
+                    .len()
+                    .cmp(&b_num.len())
+                    .then_with(|| a_num.cmp(b_num))
+                    .then_with(|| a_run.len().cmp(&b_run.len()))
+            }
+            _ => a_chars.next().cmp(&b_chars.next()),
+        };
+
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+}
+
+fn take_digits(chars: &mut Peekable<Chars>) -> String {
+    let mut digits = String::new();
+    while let Some(c) = chars.next_if(|c| c.is_ascii_digit()) {
+        digits.push(c);
+    }
+    digits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn digit_runs_compare_as_numbers() {
+        assert_eq!(natural_cmp("file2", "file10"), Ordering::Less);
+        assert_eq!(natural_cmp("file10", "file9"), Ordering::Greater);
+        assert_eq!(natural_cmp("v1.10", "v1.9"), Ordering::Greater);
+        assert_eq!(natural_cmp("file2", "file2"), Ordering::Equal);
+    }
+
+    #[test]
+    fn leading_zeros_are_decided_by_run_length() {
+        assert_eq!(natural_cmp("a01", "a1"), Ordering::Greater);
+        assert_eq!(natural_cmp("a1", "a01"), Ordering::Less);
+        assert_eq!(natural_cmp("a001", "a2"), Ordering::Less);
+        assert_eq!(natural_cmp("a0", "a00"), Ordering::Less);
+    }
+
+    #[test]
+    fn digits_before_letters() {
+        assert_eq!(natural_cmp("a1", "ab"), Ordering::Less);
+        assert_eq!(natural_cmp("9", "a"), Ordering::Less);
+        assert_eq!(natural_cmp("file", "file1"), Ordering::Less);
+        assert_eq!(natural_cmp("B", "a"), Ordering::Less);
+    }
+}
@@ -1,4 +1,5 @@
 pub mod baseview;
+pub mod hex;
 pub mod table;
 
 pub trait RenderView {
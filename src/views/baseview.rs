@@ -1,8 +1,11 @@
 use derive_new::new;
 
-use crate::types::primary::Value;
+use crate::types::primary::{Primitive, Value};
 
-use super::{table::TableView, RenderView};
+use super::{hex::HexView, table::TableView, RenderView};
+
+// longer ranges are shown by their bounds rather than expanded
+const MAX_RANGE_ROWS: u64 = 1000;
 
 #[derive(new)]
 pub struct BaseView<'a> {
@@ -12,16 +15,29 @@ pub struct BaseView<'a> {
 impl RenderView for BaseView<'_> {
     fn render(&self) -> Vec<String> {
         match self.value {
+            Value::List(l) if l.is_empty() => vec![],
             Value::List(l) => {
                 let view = TableView::from_values(l);
                 view.render()
             }
             Value::Object(o) => {
-                vec![]
+                let view = TableView::from_dict(o);
+                view.render()
             }
-            Value::Primitive(p) => {
-                vec![]
+            Value::Primitive(Primitive::Binary(bytes)) => HexView::new(bytes).render(),
+            Value::Primitive(Primitive::Range(start, end))
+                if start.abs_diff(*end) < MAX_RANGE_ROWS =>
+            {
+                match self.value.clone().expand_range() {
+                    Ok(values) => BaseView::new(&values).render(),
+                    Err(_) => vec![self.value.format()],
+                }
             }
+            Value::Primitive(p) => match p.format() {
+                formatted if formatted.is_empty() => vec![],
+                formatted => vec![formatted],
+            },
+            Value::Lambda(l) => vec![l.to_string()],
         }
     }
 }
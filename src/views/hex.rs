@@ -0,0 +1,55 @@
+use super::RenderView;
+
+// dumps of large files are cut short, the rest is summarized
+const MAX_BYTES: usize = 4096;
+const ROW_BYTES: usize = 16;
+
+/// xxd style dump with offsets, hex bytes and printable characters
+pub struct HexView<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> HexView<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        HexView { bytes }
+    }
+}
+
+impl RenderView for HexView<'_> {
+    fn render(&self) -> Vec<String> {
+        let shown = &self.bytes[..self.bytes.len().min(MAX_BYTES)];
+
+        let mut lines: Vec<String> = shown
+            .chunks(ROW_BYTES)
+            .enumerate()
+            .map(|(row, chunk)| {
+                let mut hex = String::new();
+                for column in 0..ROW_BYTES {
+                    match chunk.get(column) {
+                        Some(byte) => hex.push_str(&format!("{:02x} ", byte)),
+                        None => hex.push_str("   "),
+                    }
+                    // a gap between the two halves of a row
+                    if column == ROW_BYTES / 2 - 1 {
+                        hex.push(' ');
+                    }
+                }
+
+                let text: String = chunk
+                    .iter()
+                    .map(|byte| match byte {
+                        0x20..=0x7e => *byte as char,
+                        _ => '.',
+                    })
+                    .collect();
+
+                format!("{:08x}  {} |{}|", row * ROW_BYTES, hex, text)
+            })
+            .collect();
+
+        if self.bytes.len() > shown.len() {
+            lines.push(format!("… {} more bytes", self.bytes.len() - shown.len()));
+        }
+        lines
+    }
+}
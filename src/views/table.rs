@@ -1,6 +1,6 @@
 use tabled::{builder::Builder, Style};
 
-use crate::types::primary::Value;
+use crate::types::{datadict::DataDict, primary::Value};
 
 use super::RenderView;
 
@@ -11,34 +11,113 @@ pub struct TableView {
 
 impl TableView {
     pub fn from_values(values: &Vec<Value>) -> Self {
-        let descriptors = &values[0].data_descriptors();
+        // lists of primitives are shown as a single column
+        if !matches!(values[0], Value::Object(_)) {
+            let headers = vec!["value".to_string()];
+            let records = values.iter().map(|value| vec![value.format()]).collect();
+            return TableView { headers, records };
+        }
+
+        // rows with a children column, e.g. ps --tree, are drawn as an indented tree
+        let tree = values.iter().any(has_children);
+        let mut rows = vec![];
+        match tree {
+            true => flatten_tree(values, 0, &mut rows),
+            false => rows.extend(values.iter().map(|value| (0, value))),
+        }
+
+        // rows may not share every column, e.g. after an outer join
+        let mut headers: Vec<String> = vec![];
+        for (_, value) in rows
+            .iter()
+            .filter(|(_, value)| matches!(value, Value::Object(_)))
+        {
+            for desc in value.data_descriptors() {
+                let nested = tree && desc.name == "children";
+                if !nested && !headers.contains(&desc.name) {
+                    headers.push(desc.name);
+                }
+            }
+        }
+
+        // the name is indented when there is one, otherwise the first column
+        let indented = headers
+            .iter()
+            .position(|header| header == "name")
+            .unwrap_or(0);
 
-        let headers = descriptors.iter().map(|desc| desc.name.clone()).collect();
         let mut records = vec![];
 
-        for value in values {
-            let row: Vec<String> = descriptors
-                .iter()
-                .map(|desc| value.get_data(desc).format())
-                .collect();
+        for (depth, value) in rows {
+            let mut row: Vec<String> = match value {
+                Value::Object(dict) => headers
+                    .iter()
+                    .map(|header| dict.get_data_from_key(header).format())
+                    .collect(),
+                other => vec![other.format()],
+            };
+            if depth > 0 {
+                if let Some(cell) = row.get_mut(indented) {
+                    *cell = format!("{}└ {}", "  ".repeat(depth - 1), cell);
+                }
+            }
             records.push(row);
         }
 
         TableView { headers, records }
     }
+
+    // records are shown vertically, one key per row
+    pub fn from_dict(dict: &DataDict) -> Self {
+        let records = dict
+            .data_descriptors()
+            .iter()
+            .map(|desc| vec![desc.name.clone(), dict.get_data(desc).format()])
+            .collect();
+
+        TableView {
+            headers: vec![],
+            records,
+        }
+    }
+}
+
+fn has_children(value: &Value) -> bool {
+    match value {
+        Value::Object(dict) => matches!(dict.get("children"), Some(Value::List(_))),
+        _ => false,
+    }
+}
+
+// depth first, so children follow their parent
+fn flatten_tree<'a>(values: &'a [Value], depth: usize, rows: &mut Vec<(usize, &'a Value)>) {
+    for value in values {
+        rows.push((depth, value));
+        if let Value::Object(dict) = value {
+            if let Some(Value::List(children)) = dict.get("children") {
+                flatten_tree(children, depth + 1, rows);
+            }
+        }
+    }
 }
 
 impl RenderView for TableView {
     fn render(&self) -> Vec<String> {
         let mut builder = Builder::default();
-        builder.set_columns(&self.headers);
+        if !self.headers.is_empty() {
+            builder.set_columns(&self.headers);
+        }
 
         for record in &self.records {
             builder.add_record(record);
         }
 
         let mut table = builder.build();
-        table.with(Style::rounded());
+        // without headers there is no header line to draw
+        match self.headers.is_empty() {
+            true => table.with(Style::rounded().off_horizontals()),
+            false => table.with(Style::rounded()),
+        };
 
         vec![table.to_string()]
     }